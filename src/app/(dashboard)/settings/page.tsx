@@ -3,6 +3,7 @@
 import { useState, useEffect } from 'react';
 import { Button } from '@/components/ui/button';
 import { Input } from '@/components/ui/input';
+import { Textarea } from '@/components/ui/textarea';
 import {
   Card,
   CardContent,
@@ -13,27 +14,23 @@ import {
 import { Separator } from '@/components/ui/separator';
 import { toast } from 'sonner';
 import { useOpenAIKey } from '@/hooks/use-openai-key';
+import { PROVIDER_DESCRIPTORS as PROVIDERS } from '@/lib/types/domain';
 import type { Provider } from '@/lib/types/domain';
 
-const PROVIDERS: { value: Provider; label: string; description: string; placeholder: string }[] = [
-  {
-    value: 'openai',
-    label: 'OpenAI',
-    description: 'GPT-4o, GPT-4.1, o4-mini, and more',
-    placeholder: 'sk-...',
-  },
-  {
-    value: 'mercury',
-    label: 'Mercury (Inception Labs)',
-    description: 'Diffusion-based LLM — 5-10x faster, $0.25/M input tokens',
-    placeholder: 'sk_...',
-  },
-];
-
 export default function SettingsPage() {
-  const { provider, setProvider, apiKey, setApiKey, clearApiKey, isLoaded, hasKey } =
-    useOpenAIKey();
+  const {
+    provider,
+    setProvider,
+    apiKey,
+    setApiKey,
+    clearApiKey,
+    extraHeaders,
+    setExtraHeaders,
+    isLoaded,
+    hasKey,
+  } = useOpenAIKey();
   const [keyInput, setKeyInput] = useState('');
+  const [headersInput, setHeadersInput] = useState('');
 
   useEffect(() => {
     if (isLoaded && apiKey) {
@@ -41,6 +38,16 @@ export default function SettingsPage() {
     }
   }, [isLoaded, apiKey]);
 
+  useEffect(() => {
+    if (isLoaded) {
+      setHeadersInput(
+        Object.entries(extraHeaders)
+          .map(([name, value]) => `${name}: ${value}`)
+          .join('\n'),
+      );
+    }
+  }, [isLoaded, extraHeaders]);
+
   const handleSave = () => {
     if (!keyInput.trim() || keyInput.includes('...')) {
       toast.error('Enter a valid API key');
@@ -57,6 +64,22 @@ export default function SettingsPage() {
     toast.success('API key removed');
   };
 
+  const handleSaveHeaders = () => {
+    const headers: Record<string, string> = {};
+    for (const line of headersInput.split('\n')) {
+      if (!line.trim()) continue;
+      const sep = line.indexOf(':');
+      const name = sep > 0 ? line.slice(0, sep).trim() : '';
+      if (!/^[!#$%&'*+\-.^_`|~0-9A-Za-z]+$/.test(name)) {
+        toast.error(`Invalid header line: ${line.trim()}`);
+        return;
+      }
+      headers[name] = line.slice(sep + 1).trim();
+    }
+    setExtraHeaders(headers);
+    toast.success('Custom headers saved');
+  };
+
   const handleProviderChange = (p: Provider) => {
     setProvider(p);
     // Clear key input when switching providers since keys differ
@@ -67,6 +90,7 @@ export default function SettingsPage() {
   };
 
   const currentProvider = PROVIDERS.find((p) => p.value === provider)!;
+  const keyField = currentProvider.fields.find((f) => f.name === 'api_key');
 
   return (
     <div className="mx-auto max-w-2xl px-6 py-10">
@@ -116,9 +140,9 @@ export default function SettingsPage() {
           <CardDescription className="text-zinc-500">
             Your API key is stored locally in your browser and sent with each
             request. It is never stored on our servers.
-            {provider === 'mercury' && (
+            {currentProvider.base_url && (
               <span className="block mt-1 text-zinc-600">
-                Base URL: https://api.inceptionlabs.ai/v1
+                Base URL: {currentProvider.base_url}
               </span>
             )}
           </CardDescription>
@@ -129,7 +153,7 @@ export default function SettingsPage() {
               type="text"
               value={keyInput}
               onChange={(e) => setKeyInput(e.target.value)}
-              placeholder={currentProvider.placeholder}
+              placeholder={keyField?.placeholder}
               className="border-zinc-700 bg-zinc-800 text-zinc-100 font-mono text-sm"
               onFocus={() => {
                 if (keyInput.includes('...')) setKeyInput('');
@@ -158,6 +182,29 @@ export default function SettingsPage() {
 
       <Separator className="my-6 bg-zinc-800" />
 
+      <Card className="border-zinc-800 bg-zinc-900">
+        <CardHeader>
+          <CardTitle className="text-lg text-zinc-100">Custom Headers</CardTitle>
+          <CardDescription className="text-zinc-500">
+            Extra headers sent with every provider request, one{' '}
+            <code>Name: value</code> per line. Useful for gateways that need a
+            tenant or custom auth header.
+          </CardDescription>
+        </CardHeader>
+        <CardContent className="space-y-4">
+          <Textarea
+            value={headersInput}
+            onChange={(e) => setHeadersInput(e.target.value)}
+            placeholder="X-Tenant-Id: acme"
+            rows={3}
+            className="border-zinc-700 bg-zinc-800 text-zinc-100 font-mono text-sm"
+          />
+          <Button onClick={handleSaveHeaders}>Save</Button>
+        </CardContent>
+      </Card>
+
+      <Separator className="my-6 bg-zinc-800" />
+
       <Card className="border-zinc-800 bg-zinc-900">
         <CardHeader>
           <CardTitle className="text-lg text-zinc-100">
@@ -0,0 +1,65 @@
+import { NextRequest, NextResponse } from 'next/server';
+import { createClient } from '@/lib/supabase/server';
+import { createOpenAIClient, parseExtraHeaders } from '@/lib/openai/client';
+import { getUserSettings } from '@/lib/supabase/queries/user-settings';
+import { MERCURY_BASE_URL } from '@/lib/types/domain';
+import type { Provider } from '@/lib/types/domain';
+
+const PROVIDERS: Provider[] = ['openai', 'mercury'];
+
+/**
+ * Picks the first provider the caller's API key actually works with, probing
+ * the saved default first, then the selected provider, then the rest.
+ */
+export async function GET(request: NextRequest) {
+  const supabase = await createClient();
+
+  const {
+    data: { user },
+  } = await supabase.auth.getUser();
+  if (!user) {
+    return NextResponse.json({ error: 'Unauthorized' }, { status: 401 });
+  }
+
+  const apiKey = request.headers.get('x-api-key') || request.headers.get('x-openai-api-key');
+  if (!apiKey) {
+    return NextResponse.json({ error: 'Missing API key' }, { status: 401 });
+  }
+
+  let extraHeaders: Record<string, string> | undefined;
+  try {
+    extraHeaders = parseExtraHeaders(request.headers.get('x-ai-extra-headers'));
+  } catch (err) {
+    return NextResponse.json(
+      { error: err instanceof Error ? err.message : String(err) },
+      { status: 400 },
+    );
+  }
+
+  const settings = await getUserSettings(supabase, user.id);
+  const preferred = [
+    settings?.default_provider,
+    request.headers.get('x-ai-provider') as Provider | null,
+  ].filter((p): p is Provider => !!p && PROVIDERS.includes(p));
+  const order = Array.from(new Set([...preferred, ...PROVIDERS]));
+
+  const failures: Record<string, string> = {};
+  for (const provider of order) {
+    const baseURL = provider === 'mercury' ? MERCURY_BASE_URL : undefined;
+    const client = createOpenAIClient(apiKey, baseURL, extraHeaders);
+    try {
+      const models = await client.models.list({ maxRetries: 0, timeout: 10_000 });
+      return NextResponse.json({
+        provider,
+        models: models.data.map((m) => m.id),
+      });
+    } catch (err) {
+      failures[provider] = err instanceof Error ? err.message : String(err);
+    }
+  }
+
+  return NextResponse.json(
+    { error: 'No provider available for this API key', failures },
+    { status: 503 },
+  );
+}
@@ -1,6 +1,10 @@
 import { NextRequest } from 'next/server';
 import { createClient } from '@/lib/supabase/server';
-import { createOpenAIClient } from '@/lib/openai/client';
+import {
+  createProgressStream,
+  readProgressEvents,
+} from '@/lib/utils/progress-stream';
+import { createOpenAIClient, parseExtraHeaders } from '@/lib/openai/client';
 import { generateText } from '@/lib/openai/text-generation';
 import {
   processTextConfigResponse,
@@ -12,10 +16,16 @@ import { createColumn, getMaxPosition } from '@/lib/supabase/queries/columns';
 import { createDataset } from '@/lib/supabase/queries/datasets';
 import { upsertProcess } from '@/lib/supabase/queries/processes';
 import { DEFAULT_MODEL, MERCURY_BASE_URL } from '@/lib/types/domain';
-import type { TaskType, Provider } from '@/lib/types/domain';
+import type {
+  DatasetGenerationProgress,
+  TaskType,
+  Provider,
+} from '@/lib/types/domain';
 
 export const maxDuration = 300;
 
+const INITIAL_ROWS = 5;
+
 export async function POST(request: NextRequest) {
   const apiKey = request.headers.get('x-api-key') || request.headers.get('x-openai-api-key');
   if (!apiKey) {
@@ -25,6 +35,17 @@ export async function POST(request: NextRequest) {
   }
   const provider = (request.headers.get('x-ai-provider') || 'openai') as Provider;
   const baseURL = provider === 'mercury' ? MERCURY_BASE_URL : undefined;
+  const rawExtraHeaders = request.headers.get('x-ai-extra-headers');
+
+  let extraHeaders: Record<string, string> | undefined;
+  try {
+    extraHeaders = parseExtraHeaders(rawExtraHeaders);
+  } catch (err) {
+    return new Response(
+      JSON.stringify({ error: err instanceof Error ? err.message : String(err) }),
+      { status: 400 },
+    );
+  }
 
   const {
     instruction,
@@ -33,7 +54,7 @@ export async function POST(request: NextRequest) {
   } = await request.json();
 
   const supabase = await createClient();
-  const openai = createOpenAIClient(apiKey, baseURL);
+  const openai = createOpenAIClient(apiKey, baseURL, extraHeaders);
 
   const {
     data: { user },
@@ -44,186 +65,194 @@ export async function POST(request: NextRequest) {
     });
   }
 
-  const encoder = new TextEncoder();
-  const stream = new ReadableStream({
-    async start(controller) {
-      const send = (event: string, data: any) => {
-        controller.enqueue(
-          encoder.encode(`data: ${JSON.stringify({ event, ...data })}\n\n`),
-        );
-      };
-
-      try {
-        // Step 1: Get dataset config from LLM
-        send('dataset.config', {});
+  return createProgressStream(async ({ send }) => {
+    try {
+      // Step 1: Get dataset config from LLM
+      send('dataset.config', {});
 
-        const promptText = search_enabled
-          ? SEARCH_PROMPT_TEMPLATE.replace('{instruction}', instruction).replace(
-              '{maxSearchQueries}',
-              '1',
-            )
-          : NO_SEARCH_PROMPT_TEMPLATE.replace('{instruction}', instruction);
+      const promptText = search_enabled
+        ? SEARCH_PROMPT_TEMPLATE.replace('{instruction}', instruction).replace(
+            '{maxSearchQueries}',
+            '1',
+          )
+        : NO_SEARCH_PROMPT_TEMPLATE.replace('{instruction}', instruction);
 
-        const configResult = await generateText(openai, promptText, model);
-
-        if (configResult.error || !configResult.value) {
-          send('dataset.config.error', {
-            error: configResult.error || 'No response from model',
-          });
-          controller.close();
-          return;
-        }
-
-        const config = processTextConfigResponse(
-          configResult.value,
-          search_enabled,
-        );
+      const configResult = await generateText(openai, promptText, model);
 
-        if (config.columns.length === 0) {
-          send('dataset.config.error', {
-            error: 'No columns found in model response',
-          });
-          controller.close();
-          return;
-        }
-
-        // Step 2: Create dataset and columns
-        send('dataset.create', { name: config.datasetName });
+      if (configResult.error || !configResult.value) {
+        send('dataset.config.error', {
+          error: configResult.error || 'No response from model',
+        });
+        return;
+      }
 
-        const dataset = await createDataset(
-          supabase,
-          config.datasetName,
-          user.id,
-        );
+      const config = processTextConfigResponse(
+        configResult.value,
+        search_enabled,
+      );
 
-        const columnNames = config.columns.map((c) => c.name);
-        const createdColumns: Array<{ id: string; name: string; type: string }> =
-          [];
+      if (config.columns.length === 0) {
+        send('dataset.config.error', {
+          error: 'No columns found in model response',
+        });
+        return;
+      }
 
-        for (let i = 0; i < config.columns.length; i++) {
-          const col = config.columns[i];
-          const isImage = col.type === 'image';
+      // Step 2: Create dataset and columns
+      send('dataset.create', { name: config.datasetName });
+
+      const dataset = await createDataset(
+        supabase,
+        config.datasetName,
+        user.id,
+      );
+
+      const columnNames = config.columns.map((c) => c.name);
+      const createdColumns: Array<{ id: string; name: string; type: string }> =
+        [];
+
+      for (let i = 0; i < config.columns.length; i++) {
+        const col = config.columns[i];
+        const isImage = col.type === 'image';
+
+        const created = await createColumn(supabase, {
+          dataset_id: dataset.id,
+          name: col.name,
+          type: isImage ? 'image' : 'text',
+          kind: 'dynamic',
+          position: i,
+        });
 
-          const created = await createColumn(supabase, {
-            dataset_id: dataset.id,
-            name: col.name,
-            type: isImage ? 'image' : 'text',
-            kind: 'dynamic',
-            position: i,
-          });
+        createdColumns.push({
+          id: created.id,
+          name: col.name,
+          type: isImage ? 'image' : 'text',
+        });
+      }
 
-          createdColumns.push({
-            id: created.id,
-            name: col.name,
-            type: isImage ? 'image' : 'text',
-          });
-        }
+      // Step 3: Create processes with column references
+      for (let i = 0; i < config.columns.length; i++) {
+        const col = config.columns[i];
+        const created = createdColumns[i];
+        const isImage = col.type === 'image';
+
+        const refs = extractColumnReferences(col.prompt, columnNames);
+        const refIds = refs
+          .map((refName) => {
+            const refCol = createdColumns.find((c) => c.name === refName);
+            return refCol?.id;
+          })
+          .filter(Boolean) as string[];
+
+        const task: TaskType = isImage ? 'text-to-image' : 'text-generation';
+
+        await upsertProcess(supabase, {
+          column_id: created.id,
+          prompt: col.prompt,
+          model: isImage ? 'gpt-image-1' : model,
+          task,
+          search_enabled,
+          columns_references: refIds,
+        });
+      }
 
-        // Step 3: Create processes with column references
-        for (let i = 0; i < config.columns.length; i++) {
-          const col = config.columns[i];
-          const created = createdColumns[i];
-          const isImage = col.type === 'image';
-
-          const refs = extractColumnReferences(col.prompt, columnNames);
-          const refIds = refs
-            .map((refName) => {
-              const refCol = createdColumns.find((c) => c.name === refName);
-              return refCol?.id;
-            })
-            .filter(Boolean) as string[];
-
-          const task: TaskType = isImage ? 'text-to-image' : 'text-generation';
-
-          await upsertProcess(supabase, {
-            column_id: created.id,
-            prompt: col.prompt,
-            model: isImage ? 'gpt-image-1' : model,
-            task,
-            search_enabled,
-            columns_references: refIds,
-          });
-        }
+      send('dataset.create.success', {
+        dataset: { id: dataset.id, name: dataset.name },
+        columns: config.columns,
+      });
+
+      // Step 4: Generate initial rows (5 per column)
+      send('dataset.populate', { dataset_id: dataset.id });
+
+      // One dataset-wide figure so the client doesn't sum per-column bars
+      const progress: DatasetGenerationProgress = {
+        total: createdColumns.length * INITIAL_ROWS,
+        completed: 0,
+        failed: 0,
+        columns: Object.fromEntries(
+          createdColumns.map((c) => [
+            c.id,
+            { total: INITIAL_ROWS, completed: 0, failed: 0 },
+          ]),
+        ),
+      };
 
-        send('dataset.create.success', {
-          dataset: { id: dataset.id, name: dataset.name },
-          columns: config.columns,
+      // Trigger generation for each column via the generate endpoint
+      // We do this inline to avoid circular HTTP calls
+      for (const col of createdColumns) {
+        const maxPos = await getMaxPosition(supabase, dataset.id);
+        send('column.generating', {
+          column_id: col.id,
+          column_name: col.name,
         });
 
-        // Step 4: Generate initial rows (5 per column)
-        send('dataset.populate', { dataset_id: dataset.id });
-
-        // Trigger generation for each column via the generate endpoint
-        // We do this inline to avoid circular HTTP calls
-        for (const col of createdColumns) {
-          const maxPos = await getMaxPosition(supabase, dataset.id);
-          send('column.generating', {
+        // Fire a fetch to our own generate endpoint
+        const generateUrl = new URL('/api/generate', request.url);
+        const colConfig = config.columns.find((c) => c.name === col.name);
+        const refs = extractColumnReferences(
+          colConfig?.prompt || '',
+          columnNames,
+        );
+        const refIds = refs
+          .map((refName) => {
+            const refCol = createdColumns.find((c) => c.name === refName);
+            return refCol?.id;
+          })
+          .filter(Boolean) as string[];
+
+        const isImage = col.type === 'image';
+
+        const res = await fetch(generateUrl.toString(), {
+          method: 'POST',
+          headers: {
+            'Content-Type': 'application/json',
+            'x-api-key': apiKey,
+            'x-ai-provider': provider,
+            ...(rawExtraHeaders && { 'x-ai-extra-headers': rawExtraHeaders }),
+            Cookie: request.headers.get('cookie') || '',
+          },
+          body: JSON.stringify({
+            dataset_id: dataset.id,
             column_id: col.id,
             column_name: col.name,
-          });
-
-          // Fire a fetch to our own generate endpoint
-          const generateUrl = new URL('/api/generate', request.url);
-          const colConfig = config.columns.find((c) => c.name === col.name);
-          const refs = extractColumnReferences(
-            colConfig?.prompt || '',
-            columnNames,
-          );
-          const refIds = refs
-            .map((refName) => {
-              const refCol = createdColumns.find((c) => c.name === refName);
-              return refCol?.id;
-            })
-            .filter(Boolean) as string[];
-
-          const isImage = col.type === 'image';
-
-          await fetch(generateUrl.toString(), {
-            method: 'POST',
-            headers: {
-              'Content-Type': 'application/json',
-              'x-api-key': apiKey,
-              'x-ai-provider': provider,
-              Cookie: request.headers.get('cookie') || '',
+            process: {
+              prompt: colConfig?.prompt || '',
+              model: isImage ? 'gpt-image-1' : model,
+              task: isImage ? 'text-to-image' : 'text-generation',
+              search_enabled,
+              columns_references: refIds,
             },
-            body: JSON.stringify({
-              dataset_id: dataset.id,
-              column_id: col.id,
-              column_name: col.name,
-              process: {
-                prompt: colConfig?.prompt || '',
-                model: isImage ? 'gpt-image-1' : model,
-                task: isImage ? 'text-to-image' : 'text-generation',
-                search_enabled,
-                columns_references: refIds,
-              },
-              offset: 0,
-              limit: 5,
-            }),
-          });
+            offset: 0,
+            limit: INITIAL_ROWS,
+          }),
+        });
 
-          send('column.generated', { column_id: col.id });
+        if (res.body) {
+          await readProgressEvents(res.body, (event) => {
+            if (event.event !== 'cell.complete') return;
+            const column = progress.columns[col.id];
+            if (event.error) {
+              column.failed++;
+              progress.failed++;
+            } else {
+              column.completed++;
+              progress.completed++;
+            }
+            send('dataset.progress', { progress });
+          });
         }
 
-        send('dataset.populate.success', {
-          dataset: { id: dataset.id, name: dataset.name },
-        });
-
-        controller.close();
-      } catch (err) {
-        send('generic.error', {
-          error: err instanceof Error ? err.message : String(err),
-        });
-        controller.close();
+        send('column.generated', { column_id: col.id });
       }
-    },
-  });
-
-  return new Response(stream, {
-    headers: {
-      'Content-Type': 'text/event-stream',
-      'Cache-Control': 'no-cache',
-      Connection: 'keep-alive',
-    },
-  });
+
+      send('dataset.populate.success', {
+        dataset: { id: dataset.id, name: dataset.name },
+        progress,
+      });
+    } catch (err) {
+      send('generic.error', {
+        error: err instanceof Error ? err.message : String(err),
+      });
+    }
+  }, user.id);
 }
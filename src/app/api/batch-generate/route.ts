@@ -0,0 +1,124 @@
+import { NextRequest } from 'next/server';
+import { createClient } from '@/lib/supabase/server';
+import { createProgressStream } from '@/lib/utils/progress-stream';
+import {
+  createOpenAIClient,
+  credentialScope,
+  parseExtraHeaders,
+} from '@/lib/openai/client';
+import { generateText, type ChatResult } from '@/lib/openai/text-generation';
+import { withProviderSlot } from '@/lib/openai/provider-limiter';
+import { materializePrompt } from '@/lib/utils/prompt-template';
+import {
+  DEFAULT_MODEL,
+  MAX_CONCURRENCY,
+  MAX_ROWS_IMPORT,
+  MERCURY_BASE_URL,
+} from '@/lib/types/domain';
+import type { Provider } from '@/lib/types/domain';
+
+export const maxDuration = 300;
+
+/**
+ * Runs a prompt template over a list of ad-hoc inputs without a dataset.
+ * Each input map fills the template's `{{key}}` references exactly like a
+ * row does, and results come back in input order. Nothing is stored.
+ */
+export async function POST(request: NextRequest) {
+  const apiKey = request.headers.get('x-api-key') || request.headers.get('x-openai-api-key');
+  if (!apiKey) {
+    return new Response(JSON.stringify({ error: 'Missing API key' }), {
+      status: 401,
+    });
+  }
+  const provider = (request.headers.get('x-ai-provider') || 'openai') as Provider;
+  const baseURL = provider === 'mercury' ? MERCURY_BASE_URL : undefined;
+
+  let extraHeaders: Record<string, string> | undefined;
+  try {
+    extraHeaders = parseExtraHeaders(request.headers.get('x-ai-extra-headers'));
+  } catch (err) {
+    return new Response(
+      JSON.stringify({ error: err instanceof Error ? err.message : String(err) }),
+      { status: 400 },
+    );
+  }
+
+  const {
+    prompt,
+    inputs,
+    model = DEFAULT_MODEL,
+  }: {
+    prompt: string;
+    inputs: Record<string, string>[];
+    model?: string;
+  } = await request.json();
+
+  if (!prompt || !Array.isArray(inputs) || inputs.length === 0) {
+    return new Response(JSON.stringify({ error: 'Missing prompt or inputs' }), {
+      status: 400,
+    });
+  }
+  if (inputs.length > MAX_ROWS_IMPORT) {
+    return new Response(
+      JSON.stringify({ error: `A batch is limited to ${MAX_ROWS_IMPORT} inputs` }),
+      { status: 400 },
+    );
+  }
+
+  const supabase = await createClient();
+  const {
+    data: { user },
+  } = await supabase.auth.getUser();
+  if (!user) {
+    return new Response(JSON.stringify({ error: 'Unauthorized' }), {
+      status: 401,
+    });
+  }
+
+  const openai = createOpenAIClient(apiKey, baseURL, extraHeaders);
+
+  return createProgressStream(async ({ send, signal }) => {
+    const results: Array<Pick<ChatResult, 'value' | 'error'> | null> =
+      new Array(inputs.length).fill(null);
+    let completed = 0;
+    let failed = 0;
+
+    for (let i = 0; i < inputs.length && !signal.aborted; i += MAX_CONCURRENCY) {
+      await Promise.all(
+        inputs.slice(i, i + MAX_CONCURRENCY).map(async (input, j) => {
+          const index = i + j;
+          let result: ChatResult;
+          try {
+            const finalPrompt = materializePrompt({
+              instruction: prompt,
+              data: input,
+              task: 'text-generation',
+            });
+            result = await withProviderSlot(
+              credentialScope(apiKey),
+              provider,
+              () => generateText(openai, finalPrompt, model, { signal }),
+              signal,
+            );
+          } catch (err) {
+            result = { error: err instanceof Error ? err.message : String(err) };
+          }
+
+          results[index] = { value: result.value, error: result.error };
+          if (result.error) {
+            failed++;
+          } else {
+            completed++;
+          }
+          send('batch.item', { index, value: result.value, error: result.error });
+        }),
+      );
+    }
+
+    send(signal.aborted ? 'batch.aborted' : 'batch.complete', {
+      progress: { total: inputs.length, completed, failed },
+      results,
+    });
+  }, user.id);
+}
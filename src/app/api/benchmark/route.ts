@@ -0,0 +1,144 @@
+import { NextRequest } from 'next/server';
+import { createClient } from '@/lib/supabase/server';
+import { createProgressStream } from '@/lib/utils/progress-stream';
+import {
+  createOpenAIClient,
+  credentialScope,
+  parseExtraHeaders,
+} from '@/lib/openai/client';
+import { generateText, type ChatResult } from '@/lib/openai/text-generation';
+import { withProviderSlot } from '@/lib/openai/provider-limiter';
+import { estimateTokens } from '@/lib/utils/token-estimate';
+import {
+  DEFAULT_MODEL,
+  MAX_CONCURRENCY,
+  MERCURY_BASE_URL,
+} from '@/lib/types/domain';
+import type { Provider } from '@/lib/types/domain';
+
+export const maxDuration = 300;
+
+const MAX_REQUESTS = 200;
+
+const percentile = (sorted: number[], p: number) =>
+  sorted.length === 0
+    ? 0
+    : sorted[Math.min(sorted.length - 1, Math.ceil((p / 100) * sorted.length) - 1)];
+
+/**
+ * Measures throughput for a provider and model by running the sample prompts
+ * `iterations` times at the same concurrency as a real generation run.
+ * Nothing is written to the dataset tables.
+ */
+export async function POST(request: NextRequest) {
+  const apiKey = request.headers.get('x-api-key') || request.headers.get('x-openai-api-key');
+  if (!apiKey) {
+    return new Response(JSON.stringify({ error: 'Missing API key' }), {
+      status: 401,
+    });
+  }
+  const provider = (request.headers.get('x-ai-provider') || 'openai') as Provider;
+  const baseURL = provider === 'mercury' ? MERCURY_BASE_URL : undefined;
+
+  let extraHeaders: Record<string, string> | undefined;
+  try {
+    extraHeaders = parseExtraHeaders(request.headers.get('x-ai-extra-headers'));
+  } catch (err) {
+    return new Response(
+      JSON.stringify({ error: err instanceof Error ? err.message : String(err) }),
+      { status: 400 },
+    );
+  }
+
+  const {
+    model = DEFAULT_MODEL,
+    prompts,
+    iterations = 1,
+  }: { model?: string; prompts: string[]; iterations?: number } =
+    await request.json();
+
+  if (!Array.isArray(prompts) || prompts.length === 0) {
+    return new Response(JSON.stringify({ error: 'Missing prompts' }), {
+      status: 400,
+    });
+  }
+  if (prompts.length * iterations > MAX_REQUESTS) {
+    return new Response(
+      JSON.stringify({ error: `A benchmark is limited to ${MAX_REQUESTS} requests` }),
+      { status: 400 },
+    );
+  }
+
+  const supabase = await createClient();
+  const {
+    data: { user },
+  } = await supabase.auth.getUser();
+  if (!user) {
+    return new Response(JSON.stringify({ error: 'Unauthorized' }), {
+      status: 401,
+    });
+  }
+
+  const openai = createOpenAIClient(apiKey, baseURL, extraHeaders);
+
+  return createProgressStream(async ({ send, signal }) => {
+    const queue = Array.from({ length: iterations }, () => prompts).flat();
+    const latencies: number[] = [];
+    let outputTokens = 0;
+    let failed = 0;
+
+    const startedAt = performance.now();
+    for (let i = 0; i < queue.length && !signal.aborted; i += MAX_CONCURRENCY) {
+      await Promise.all(
+        queue.slice(i, i + MAX_CONCURRENCY).map(async (prompt) => {
+          // Latency is timed from when the slot is granted, not while queued
+          let requestStart = performance.now();
+          let result: ChatResult;
+          try {
+            result = await withProviderSlot(
+              credentialScope(apiKey),
+              provider,
+              () => {
+                requestStart = performance.now();
+                return generateText(openai, prompt, model, { signal });
+              },
+              signal,
+            );
+          } catch (err) {
+            result = { error: err instanceof Error ? err.message : String(err) };
+          }
+          if (result.error) {
+            failed++;
+            return;
+          }
+          latencies.push(performance.now() - requestStart);
+          outputTokens += estimateTokens(result.value || '');
+        }),
+      );
+      send('benchmark.progress', {
+        completed: latencies.length,
+        failed,
+        total: queue.length,
+      });
+    }
+    const elapsedSecs = (performance.now() - startedAt) / 1000;
+
+    const sorted = [...latencies].sort((a, b) => a - b);
+    send(signal.aborted ? 'benchmark.aborted' : 'benchmark.complete', {
+      provider,
+      model,
+      requests: latencies.length,
+      failed,
+      elapsed_secs: Math.round(elapsedSecs * 100) / 100,
+      cells_per_minute:
+        elapsedSecs > 0 ? Math.round((latencies.length / elapsedSecs) * 60) : 0,
+      // Estimated from output length, not provider-reported usage
+      output_tokens_per_sec:
+        elapsedSecs > 0 ? Math.round(outputTokens / elapsedSecs) : 0,
+      latency_ms: {
+        p50: Math.round(percentile(sorted, 50)),
+        p95: Math.round(percentile(sorted, 95)),
+      },
+    });
+  }, user.id);
+}
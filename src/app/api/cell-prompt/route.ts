@@ -0,0 +1,45 @@
+import { NextRequest, NextResponse } from 'next/server';
+import { createClient } from '@/lib/supabase/server';
+import { getCellMeta } from '@/lib/supabase/queries/cell-meta';
+
+/**
+ * Returns the exact prompt that produced a generated cell. Prompts are only
+ * recorded while the user's `store_prompts` setting is on.
+ */
+export async function GET(request: NextRequest) {
+  const supabase = await createClient();
+
+  const {
+    data: { user },
+  } = await supabase.auth.getUser();
+  if (!user) {
+    return NextResponse.json({ error: 'Unauthorized' }, { status: 401 });
+  }
+
+  const { searchParams } = new URL(request.url);
+  const columnId = searchParams.get('column_id');
+  const rowIdx = Number(searchParams.get('row_idx'));
+
+  if (!columnId || !Number.isInteger(rowIdx)) {
+    return NextResponse.json(
+      { error: 'Missing column_id or row_idx' },
+      { status: 400 },
+    );
+  }
+
+  try {
+    const meta = await getCellMeta(supabase, columnId, rowIdx);
+    if (!meta?.prompt_used) {
+      return NextResponse.json(
+        { error: 'No prompt recorded for this cell' },
+        { status: 404 },
+      );
+    }
+    return NextResponse.json({ prompt: meta.prompt_used });
+  } catch (err) {
+    return NextResponse.json(
+      { error: err instanceof Error ? err.message : String(err) },
+      { status: 500 },
+    );
+  }
+}
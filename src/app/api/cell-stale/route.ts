@@ -0,0 +1,63 @@
+import { NextRequest, NextResponse } from 'next/server';
+import { createClient } from '@/lib/supabase/server';
+import { getCellMeta } from '@/lib/supabase/queries/cell-meta';
+import { getRowData } from '@/lib/supabase/queries/cells';
+import { getProcess } from '@/lib/supabase/queries/processes';
+import { hashCellInputs } from '@/lib/utils/cell-hash';
+
+/**
+ * Whether a generated cell's inputs changed since it was generated, by
+ * comparing its stored hash against one computed from the current row.
+ */
+export async function GET(request: NextRequest) {
+  const supabase = await createClient();
+
+  const {
+    data: { user },
+  } = await supabase.auth.getUser();
+  if (!user) {
+    return NextResponse.json({ error: 'Unauthorized' }, { status: 401 });
+  }
+
+  const { searchParams } = new URL(request.url);
+  const columnId = searchParams.get('column_id');
+  const rowIdx = Number(searchParams.get('row_idx'));
+
+  if (!columnId || !Number.isInteger(rowIdx)) {
+    return NextResponse.json(
+      { error: 'Missing column_id or row_idx' },
+      { status: 400 },
+    );
+  }
+
+  try {
+    const process = await getProcess(supabase, columnId);
+    if (!process) {
+      return NextResponse.json(
+        { error: 'Column has no process configured' },
+        { status: 400 },
+      );
+    }
+
+    const meta = await getCellMeta(supabase, columnId, rowIdx);
+    if (!meta?.prompt_hash) {
+      return NextResponse.json({ stale: true, reason: 'missing_hash' });
+    }
+
+    const data = await getRowData(
+      supabase,
+      rowIdx,
+      process.process_columns.map((pc: { column_id: string }) => pc.column_id),
+    );
+    const stale = hashCellInputs(process, data) !== meta.prompt_hash;
+    return NextResponse.json({
+      stale,
+      ...(stale && { reason: 'inputs_changed' }),
+    });
+  } catch (err) {
+    return NextResponse.json(
+      { error: err instanceof Error ? err.message : String(err) },
+      { status: 500 },
+    );
+  }
+}
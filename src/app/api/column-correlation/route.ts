@@ -0,0 +1,59 @@
+import { NextRequest, NextResponse } from 'next/server';
+import { createClient } from '@/lib/supabase/server';
+import { getColumnCorrelation } from '@/lib/supabase/queries/columns';
+
+/**
+ * Pearson correlation matrix of numeric columns, e.g. to check a generated
+ * score tracks an existing rating. Non-numeric or missing values are
+ * excluded pairwise, so each pair reports its own sample size.
+ */
+export async function GET(request: NextRequest) {
+  const supabase = await createClient();
+
+  const {
+    data: { user },
+  } = await supabase.auth.getUser();
+  if (!user) {
+    return NextResponse.json({ error: 'Unauthorized' }, { status: 401 });
+  }
+
+  const { searchParams } = new URL(request.url);
+  const columnIds = (searchParams.get('column_ids') || '')
+    .split(',')
+    .map((id) => id.trim())
+    .filter(Boolean);
+
+  if (columnIds.length < 2) {
+    return NextResponse.json(
+      { error: 'column_ids must list at least two columns' },
+      { status: 400 },
+    );
+  }
+
+  try {
+    const pairs = await getColumnCorrelation(supabase, columnIds);
+
+    const index = new Map(columnIds.map((id, i) => [id, i]));
+    const matrix: (number | null)[][] = columnIds.map((_, i) =>
+      columnIds.map((_, j) => (i === j ? 1 : null)),
+    );
+    const sampleSizes: number[][] = columnIds.map(() => columnIds.map(() => 0));
+    for (const pair of pairs) {
+      const a = index.get(pair.column_a)!;
+      const b = index.get(pair.column_b)!;
+      matrix[a][b] = matrix[b][a] = pair.correlation;
+      sampleSizes[a][b] = sampleSizes[b][a] = Number(pair.sample_size);
+    }
+
+    return NextResponse.json({
+      column_ids: columnIds,
+      matrix,
+      sample_sizes: sampleSizes,
+    });
+  } catch (err) {
+    return NextResponse.json(
+      { error: err instanceof Error ? err.message : String(err) },
+      { status: 500 },
+    );
+  }
+}
@@ -0,0 +1,31 @@
+import { NextRequest, NextResponse } from 'next/server';
+import { createClient } from '@/lib/supabase/server';
+import { getColumnDependents } from '@/lib/supabase/queries/columns';
+
+export async function GET(request: NextRequest) {
+  const supabase = await createClient();
+
+  const {
+    data: { user },
+  } = await supabase.auth.getUser();
+  if (!user) {
+    return NextResponse.json({ error: 'Unauthorized' }, { status: 401 });
+  }
+
+  const { searchParams } = new URL(request.url);
+  const columnId = searchParams.get('column_id');
+
+  if (!columnId) {
+    return NextResponse.json({ error: 'Missing column_id' }, { status: 400 });
+  }
+
+  try {
+    const dependents = await getColumnDependents(supabase, columnId);
+    return NextResponse.json({ dependents });
+  } catch (err) {
+    return NextResponse.json(
+      { error: err instanceof Error ? err.message : String(err) },
+      { status: 500 },
+    );
+  }
+}
@@ -0,0 +1,78 @@
+import { NextRequest, NextResponse } from 'next/server';
+import { createClient } from '@/lib/supabase/server';
+import {
+  getColumnSnapshots,
+  getSnapshotValues,
+} from '@/lib/supabase/queries/column-snapshots';
+import { getColumnCellValues } from '@/lib/supabase/queries/cells';
+
+/**
+ * Compares two snapshots of a column (or a snapshot against the live values
+ * when `run_b` is "current") and returns the rows whose values differ.
+ */
+export async function GET(request: NextRequest) {
+  const supabase = await createClient();
+
+  const {
+    data: { user },
+  } = await supabase.auth.getUser();
+  if (!user) {
+    return NextResponse.json({ error: 'Unauthorized' }, { status: 401 });
+  }
+
+  const { searchParams } = new URL(request.url);
+  const columnId = searchParams.get('column_id');
+  const runA = searchParams.get('run_a');
+  const runB = searchParams.get('run_b') || 'current';
+
+  if (!columnId || !runA) {
+    return NextResponse.json(
+      { error: 'Missing column_id or run_a' },
+      { status: 400 },
+    );
+  }
+
+  try {
+    // Snapshot ids are only meaningful for the column they were taken of
+    const snapshotIds = new Set(
+      (await getColumnSnapshots(supabase, columnId)).map((s: any) => s.id),
+    );
+    const foreign = [runA, runB].find(
+      (id) => id !== 'current' && !snapshotIds.has(id),
+    );
+    if (foreign) {
+      return NextResponse.json(
+        { error: `Snapshot ${foreign} does not belong to this column` },
+        { status: 400 },
+      );
+    }
+
+    const [before, after] = await Promise.all([
+      getSnapshotValues(supabase, runA),
+      runB === 'current'
+        ? getColumnCellValues(supabase, columnId)
+        : getSnapshotValues(supabase, runB),
+    ]);
+
+    const beforeMap = new Map(before.map((c) => [c.row_idx, c.value]));
+    const afterMap = new Map(after.map((c) => [c.row_idx, c.value]));
+    const rowIdxs = Array.from(
+      new Set([...beforeMap.keys(), ...afterMap.keys()]),
+    ).sort((a, b) => a - b);
+
+    const changes = rowIdxs
+      .map((rowIdx) => ({
+        row_idx: rowIdx,
+        old: beforeMap.get(rowIdx) ?? null,
+        new: afterMap.get(rowIdx) ?? null,
+      }))
+      .filter((c) => JSON.stringify(c.old) !== JSON.stringify(c.new));
+
+    return NextResponse.json({ changed: changes.length, changes });
+  } catch (err) {
+    return NextResponse.json(
+      { error: err instanceof Error ? err.message : String(err) },
+      { status: 500 },
+    );
+  }
+}
@@ -0,0 +1,45 @@
+import { NextRequest, NextResponse } from 'next/server';
+import { createClient } from '@/lib/supabase/server';
+import { bulkUpsertCellValues } from '@/lib/supabase/queries/cells';
+
+/**
+ * Fills a column top-down from a pasted list, one cell per line. Rows beyond
+ * the end of the list keep their existing values.
+ */
+export async function POST(request: NextRequest) {
+  const supabase = await createClient();
+  const {
+    data: { user },
+  } = await supabase.auth.getUser();
+
+  if (!user) {
+    return NextResponse.json({ error: 'Unauthorized' }, { status: 401 });
+  }
+
+  const { dataset_id, column_id, lines } = await request.json();
+
+  if (!dataset_id || !column_id || !Array.isArray(lines)) {
+    return NextResponse.json(
+      { error: 'Missing dataset_id, column_id or lines' },
+      { status: 400 },
+    );
+  }
+
+  const cellValues = lines.map((line: unknown, rowIdx: number) => ({
+    dataset_id,
+    column_id,
+    row_idx: rowIdx,
+    value: line == null || line === '' ? null : String(line),
+  }));
+
+  try {
+    await bulkUpsertCellValues(supabase, cellValues);
+  } catch (err) {
+    return NextResponse.json(
+      { error: err instanceof Error ? err.message : String(err) },
+      { status: 500 },
+    );
+  }
+
+  return NextResponse.json({ rows: cellValues.length });
+}
@@ -0,0 +1,72 @@
+import { NextRequest, NextResponse } from 'next/server';
+import { createClient } from '@/lib/supabase/server';
+import {
+  changeColumnKind,
+  getColumn,
+  getDatasetColumns,
+} from '@/lib/supabase/queries/columns';
+import { extractColumnReferences } from '@/lib/utils/prompt-template';
+import type { Provider } from '@/lib/types/domain';
+
+/**
+ * Turns an imported column into a generated one or back. Making a column
+ * dynamic needs a prompt and, with `clear_cells`, resets its values to
+ * pending; making it static keeps the generated values as data.
+ */
+export async function POST(request: NextRequest) {
+  const supabase = await createClient();
+  const {
+    data: { user },
+  } = await supabase.auth.getUser();
+
+  if (!user) {
+    return NextResponse.json({ error: 'Unauthorized' }, { status: 401 });
+  }
+
+  const {
+    column_id,
+    kind,
+    prompt,
+    model,
+    clear_cells = false,
+  } = await request.json();
+  if (!column_id || (kind !== 'static' && kind !== 'dynamic')) {
+    return NextResponse.json(
+      { error: 'Missing column_id or kind' },
+      { status: 400 },
+    );
+  }
+  if (kind === 'dynamic' && !prompt?.trim()) {
+    return NextResponse.json(
+      { error: 'A prompt is required to make a column dynamic' },
+      { status: 400 },
+    );
+  }
+
+  try {
+    let referenceIds: string[] = [];
+    if (kind === 'dynamic') {
+      const column = await getColumn(supabase, column_id);
+      const columns = (await getDatasetColumns(supabase, column.dataset_id))
+        .filter((c: any) => c.id !== column_id);
+      referenceIds = extractColumnReferences(
+        prompt,
+        columns.map((c: any) => c.name),
+      ).map((name) => columns.find((c: any) => c.name === name)!.id);
+    }
+
+    await changeColumnKind(supabase, column_id, kind, {
+      prompt,
+      model,
+      provider: request.headers.get('x-ai-provider') as Provider | null,
+      referenceIds,
+      clearCells: clear_cells,
+    });
+    return NextResponse.json({ success: true });
+  } catch (err) {
+    return NextResponse.json(
+      { error: err instanceof Error ? err.message : String(err) },
+      { status: 500 },
+    );
+  }
+}
@@ -0,0 +1,50 @@
+import { NextRequest, NextResponse } from 'next/server';
+import { createClient } from '@/lib/supabase/server';
+import { getColumnCellValues } from '@/lib/supabase/queries/cells';
+import { parseCellNumber } from '@/lib/utils/cell-number';
+
+/**
+ * A column's values as numbers indexed by row, for charts and stats. Empty
+ * and non-numeric cells are null; non-numeric ones are also counted so the
+ * caller can warn about them.
+ */
+export async function GET(request: NextRequest) {
+  const supabase = await createClient();
+
+  const {
+    data: { user },
+  } = await supabase.auth.getUser();
+  if (!user) {
+    return NextResponse.json({ error: 'Unauthorized' }, { status: 401 });
+  }
+
+  const { searchParams } = new URL(request.url);
+  const columnId = searchParams.get('column_id');
+
+  if (!columnId) {
+    return NextResponse.json({ error: 'Missing column_id' }, { status: 400 });
+  }
+
+  try {
+    const cells = await getColumnCellValues(supabase, columnId);
+    const rowCount = cells.reduce((max, c) => Math.max(max, c.row_idx + 1), 0);
+    const values: (number | null)[] = new Array(rowCount).fill(null);
+    let unparseable = 0;
+
+    for (const cell of cells) {
+      const num = parseCellNumber(cell.value);
+      if (Number.isNaN(num)) {
+        unparseable++;
+      } else {
+        values[cell.row_idx] = num;
+      }
+    }
+
+    return NextResponse.json({ values, unparseable });
+  } catch (err) {
+    return NextResponse.json(
+      { error: err instanceof Error ? err.message : String(err) },
+      { status: 500 },
+    );
+  }
+}
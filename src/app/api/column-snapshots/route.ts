@@ -0,0 +1,46 @@
+import { NextRequest, NextResponse } from 'next/server';
+import { createClient } from '@/lib/supabase/server';
+import {
+  getColumnSnapshots,
+  snapshotColumn,
+} from '@/lib/supabase/queries/column-snapshots';
+
+export async function GET(request: NextRequest) {
+  const supabase = await createClient();
+
+  const {
+    data: { user },
+  } = await supabase.auth.getUser();
+  if (!user) {
+    return NextResponse.json({ error: 'Unauthorized' }, { status: 401 });
+  }
+
+  const { searchParams } = new URL(request.url);
+  const columnId = searchParams.get('column_id');
+
+  if (!columnId) {
+    return NextResponse.json({ error: 'Missing column_id' }, { status: 400 });
+  }
+
+  const snapshots = await getColumnSnapshots(supabase, columnId);
+  return NextResponse.json({ snapshots });
+}
+
+export async function POST(request: NextRequest) {
+  const supabase = await createClient();
+
+  const {
+    data: { user },
+  } = await supabase.auth.getUser();
+  if (!user) {
+    return NextResponse.json({ error: 'Unauthorized' }, { status: 401 });
+  }
+
+  const { column_id, label } = await request.json();
+  if (!column_id) {
+    return NextResponse.json({ error: 'Missing column_id' }, { status: 400 });
+  }
+
+  const snapshotId = await snapshotColumn(supabase, column_id, label);
+  return NextResponse.json({ snapshot_id: snapshotId });
+}
@@ -0,0 +1,74 @@
+import { NextRequest, NextResponse } from 'next/server';
+import { createClient } from '@/lib/supabase/server';
+import { getDatasetColumns } from '@/lib/supabase/queries/columns';
+import { getCellValues } from '@/lib/supabase/queries/cells';
+
+const MAX_LIMIT = 1000;
+
+/**
+ * Returns a page of the dataset as one array of values per column, aligned by
+ * row index, with null for missing cells. Cheaper to ship and plot than one
+ * object per cell.
+ */
+export async function GET(request: NextRequest) {
+  const supabase = await createClient();
+
+  const {
+    data: { user },
+  } = await supabase.auth.getUser();
+  if (!user) {
+    return NextResponse.json({ error: 'Unauthorized' }, { status: 401 });
+  }
+
+  const { searchParams } = new URL(request.url);
+  const datasetId = searchParams.get('dataset_id');
+  const offset = Math.max(Number(searchParams.get('offset')) || 0, 0);
+  const limit = Math.min(Number(searchParams.get('limit')) || 100, MAX_LIMIT);
+
+  if (!datasetId) {
+    return NextResponse.json(
+      { error: 'Missing dataset_id' },
+      { status: 400 },
+    );
+  }
+
+  try {
+    const columns = await getDatasetColumns(supabase, datasetId);
+    // One query per column keeps each response under PostgREST's row cap
+    const cells = (
+      await Promise.all(
+        columns.map((c: any) =>
+          getCellValues(supabase, datasetId, [c.id], offset, limit),
+        ),
+      )
+    ).flat();
+
+    // Trim the page to the last row that actually has a value
+    const rowCount = cells.reduce(
+      (max: number, cell: any) => Math.max(max, cell.row_idx - offset + 1),
+      0,
+    );
+
+    const values: Record<string, any[]> = Object.fromEntries(
+      columns.map((c: any) => [c.id, new Array(rowCount).fill(null)]),
+    );
+    for (const cell of cells) {
+      values[cell.column_id][cell.row_idx - offset] = cell.value;
+    }
+
+    return NextResponse.json({
+      offset,
+      row_count: rowCount,
+      columns: columns.map((c: any) => ({
+        id: c.id,
+        name: c.name,
+        values: values[c.id],
+      })),
+    });
+  } catch (err) {
+    return NextResponse.json(
+      { error: err instanceof Error ? err.message : String(err) },
+      { status: 500 },
+    );
+  }
+}
@@ -0,0 +1,51 @@
+import { NextRequest, NextResponse } from 'next/server';
+import { createClient } from '@/lib/supabase/server';
+import { concatColumns, getColumn } from '@/lib/supabase/queries/columns';
+
+export async function POST(request: NextRequest) {
+  const supabase = await createClient();
+  const {
+    data: { user },
+  } = await supabase.auth.getUser();
+
+  if (!user) {
+    return NextResponse.json({ error: 'Unauthorized' }, { status: 401 });
+  }
+
+  const {
+    dataset_id,
+    source_column_ids,
+    separator = ' ',
+    new_column_name,
+    skip_empty = true,
+  } = await request.json();
+
+  if (
+    !dataset_id ||
+    !new_column_name?.trim() ||
+    !Array.isArray(source_column_ids) ||
+    source_column_ids.length === 0
+  ) {
+    return NextResponse.json(
+      { error: 'Missing dataset_id, source_column_ids or new_column_name' },
+      { status: 400 },
+    );
+  }
+
+  try {
+    const columnId = await concatColumns(supabase, {
+      dataset_id,
+      source_column_ids,
+      separator,
+      new_column_name: new_column_name.trim(),
+      skip_empty,
+    });
+    const column = await getColumn(supabase, columnId);
+    return NextResponse.json({ column });
+  } catch (err) {
+    return NextResponse.json(
+      { error: err instanceof Error ? err.message : String(err) },
+      { status: 500 },
+    );
+  }
+}
@@ -0,0 +1,38 @@
+import { NextRequest, NextResponse } from 'next/server';
+import { createClient } from '@/lib/supabase/server';
+import {
+  estimateTokens,
+  getContextWindow,
+} from '@/lib/utils/token-estimate';
+import { DEFAULT_MODEL } from '@/lib/types/domain';
+
+/**
+ * Estimates the tokens in a text for a model without calling a provider,
+ * using the same heuristic as the context-window guard in /api/generate.
+ */
+export async function POST(request: NextRequest) {
+  const supabase = await createClient();
+
+  const {
+    data: { user },
+  } = await supabase.auth.getUser();
+  if (!user) {
+    return NextResponse.json({ error: 'Unauthorized' }, { status: 401 });
+  }
+
+  const { text, model = DEFAULT_MODEL } = await request.json();
+  if (typeof text !== 'string') {
+    return NextResponse.json({ error: 'Missing text' }, { status: 400 });
+  }
+
+  const tokens = estimateTokens(text);
+  const contextWindow = getContextWindow(model);
+  return NextResponse.json({
+    model,
+    tokens,
+    // No tokenizer is bundled, so counts are approximate for every model
+    method: 'estimate',
+    context_window: contextWindow,
+    fits: tokens <= contextWindow,
+  });
+}
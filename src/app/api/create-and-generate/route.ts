@@ -0,0 +1,171 @@
+import { NextRequest } from 'next/server';
+import { createClient } from '@/lib/supabase/server';
+import {
+  abortOperation,
+  createProgressStream,
+  readProgressEvents,
+} from '@/lib/utils/progress-stream';
+import {
+  deleteColumn,
+  getDatasetColumns,
+  getMaxPosition,
+  insertColumnAt,
+} from '@/lib/supabase/queries/columns';
+import { upsertProcess } from '@/lib/supabase/queries/processes';
+import { extractColumnReferences } from '@/lib/utils/prompt-template';
+import { DEFAULT_MODEL } from '@/lib/types/domain';
+import type { Provider, TaskType } from '@/lib/types/domain';
+
+export const maxDuration = 300;
+
+/**
+ * Creates a generated column from a prompt and generates it in one request.
+ * The column and its process are created up front and removed again if either
+ * fails; generation progress is then streamed as from /api/generate.
+ */
+export async function POST(request: NextRequest) {
+  const apiKey = request.headers.get('x-api-key') || request.headers.get('x-openai-api-key');
+  if (!apiKey) {
+    return new Response(JSON.stringify({ error: 'Missing API key' }), {
+      status: 401,
+    });
+  }
+
+  const supabase = await createClient();
+  const {
+    data: { user },
+  } = await supabase.auth.getUser();
+  if (!user) {
+    return new Response(JSON.stringify({ error: 'Unauthorized' }), {
+      status: 401,
+    });
+  }
+
+  const {
+    dataset_id,
+    name,
+    prompt,
+    model = DEFAULT_MODEL,
+    task = 'text-generation',
+    search_enabled = false,
+    position,
+    offset = 0,
+    limit,
+  }: {
+    dataset_id: string;
+    name: string;
+    prompt: string;
+    model?: string;
+    task?: TaskType;
+    search_enabled?: boolean;
+    // Inserted before the column currently at this position; appended if unset
+    position?: number;
+    offset?: number;
+    limit: number;
+  } = await request.json();
+
+  if (!dataset_id || !name || !prompt || !limit) {
+    return new Response(
+      JSON.stringify({ error: 'Missing dataset_id, name, prompt or limit' }),
+      { status: 400 },
+    );
+  }
+
+  let columnId: string;
+  try {
+    const columns = await getDatasetColumns(supabase, dataset_id);
+    const refs = extractColumnReferences(
+      prompt,
+      columns.map((c: any) => c.name),
+    ).map((refName) => columns.find((c: any) => c.name === refName)!.id);
+
+    const maxPosition = (await getMaxPosition(supabase, dataset_id)) + 1;
+    columnId = await insertColumnAt(supabase, {
+      dataset_id,
+      name,
+      kind: 'dynamic',
+      position:
+        position != null && position < maxPosition ? position : maxPosition,
+    });
+
+    try {
+      await upsertProcess(supabase, {
+        column_id: columnId,
+        prompt,
+        model,
+        task,
+        provider: request.headers.get('x-ai-provider') as Provider | null,
+        search_enabled,
+        columns_references: refs,
+      });
+    } catch (err) {
+      await deleteColumn(supabase, columnId);
+      throw err;
+    }
+  } catch (err) {
+    return new Response(
+      JSON.stringify({ error: err instanceof Error ? err.message : String(err) }),
+      { status: 500 },
+    );
+  }
+
+  const rawExtraHeaders = request.headers.get('x-ai-extra-headers');
+  const rawProvider = request.headers.get('x-ai-provider');
+
+  return createProgressStream(async ({ send, signal }) => {
+    send('column.created', { column_id: columnId, column_name: name });
+
+    try {
+      const res = await fetch(new URL('/api/generate', request.url).toString(), {
+        method: 'POST',
+        headers: {
+          'Content-Type': 'application/json',
+          'x-api-key': apiKey,
+          ...(rawProvider && { 'x-ai-provider': rawProvider }),
+          ...(rawExtraHeaders && { 'x-ai-extra-headers': rawExtraHeaders }),
+          Cookie: request.headers.get('cookie') || '',
+        },
+        body: JSON.stringify({
+          dataset_id,
+          column_id: columnId,
+          offset,
+          limit,
+        }),
+        signal,
+      });
+
+      if (!res.ok || !res.body) {
+        const { error } = await res.json().catch(() => ({ error: null }));
+        send('generation.error', { error: error || 'Generation failed' });
+        return;
+      }
+
+      // The inner run outlives a dropped connection, so cancelling this
+      // operation stops it directly as well
+      const innerOperationId = res.headers.get('X-Operation-Id');
+      const stopInner = () => {
+        if (innerOperationId) abortOperation(innerOperationId, user.id);
+      };
+      signal.addEventListener('abort', stopInner, { once: true });
+
+      // Relay generation events under this operation
+      try {
+        await readProgressEvents(res.body, ({ event, ...data }) => {
+          if (event === 'heartbeat') return;
+          delete data.operation_id;
+          send(event, data);
+        });
+      } finally {
+        signal.removeEventListener('abort', stopInner);
+      }
+    } catch (err) {
+      if (signal.aborted) {
+        send('generation.aborted', { column_id: columnId });
+        return;
+      }
+      send('generation.error', {
+        error: err instanceof Error ? err.message : String(err),
+      });
+    }
+  }, user.id);
+}
@@ -0,0 +1,100 @@
+import { NextRequest, NextResponse } from 'next/server';
+import { createClient } from '@/lib/supabase/server';
+import {
+  createDataset,
+  deleteDataset,
+} from '@/lib/supabase/queries/datasets';
+import {
+  createColumn,
+  getDatasetColumns,
+} from '@/lib/supabase/queries/columns';
+import { upsertProcess } from '@/lib/supabase/queries/processes';
+import { extractColumnReferences } from '@/lib/utils/prompt-template';
+import {
+  parseDatasetSchema,
+  type DatasetSchema,
+} from '@/lib/utils/dataset-schema';
+
+/**
+ * Creates a dataset and its columns from a JSON schema kept in version
+ * control. The schema is validated before anything is written, and the
+ * dataset is removed again if creating any column fails.
+ */
+export async function POST(request: NextRequest) {
+  const supabase = await createClient();
+  const {
+    data: { user },
+  } = await supabase.auth.getUser();
+
+  if (!user) {
+    return NextResponse.json({ error: 'Unauthorized' }, { status: 401 });
+  }
+
+  const { name, schema: rawSchema } = await request.json();
+
+  let schema: DatasetSchema;
+  try {
+    schema = parseDatasetSchema(rawSchema);
+  } catch (err) {
+    return NextResponse.json(
+      { error: err instanceof Error ? err.message : String(err) },
+      { status: 400 },
+    );
+  }
+
+  const datasetName = name || schema.name;
+  if (!datasetName) {
+    return NextResponse.json({ error: 'Missing name' }, { status: 400 });
+  }
+
+  let datasetId: string | undefined;
+  try {
+    const dataset = await createDataset(
+      supabase,
+      datasetName,
+      user.id,
+      schema.default_provider,
+    );
+    datasetId = dataset.id;
+
+    const columnIds = new Map<string, string>();
+    for (const [i, col] of schema.columns.entries()) {
+      const created = await createColumn(supabase, {
+        dataset_id: dataset.id,
+        name: col.name,
+        type: col.type,
+        kind: col.prompt ? 'dynamic' : 'static',
+        position: i,
+      });
+      columnIds.set(col.name, created.id);
+    }
+
+    const columnNames = schema.columns.map((c) => c.name);
+    for (const col of schema.columns) {
+      if (!col.prompt) continue;
+      await upsertProcess(supabase, {
+        column_id: columnIds.get(col.name)!,
+        prompt: col.prompt,
+        model: col.model,
+        task: col.task,
+        provider: col.provider,
+        search_enabled: col.search_enabled,
+        columns_references: extractColumnReferences(col.prompt, columnNames)
+          .filter((ref) => ref !== col.name)
+          .map((ref) => columnIds.get(ref)!),
+      });
+    }
+
+    const columns = await getDatasetColumns(supabase, dataset.id);
+    return NextResponse.json({ dataset, columns });
+  } catch (err) {
+    // Drop the partial dataset; columns and processes cascade with it
+    if (datasetId) {
+      await deleteDataset(supabase, datasetId).catch(() => {});
+    }
+    return NextResponse.json(
+      { error: err instanceof Error ? err.message : String(err) },
+      { status: 500 },
+    );
+  }
+}
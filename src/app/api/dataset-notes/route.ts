@@ -0,0 +1,58 @@
+import { NextRequest, NextResponse } from 'next/server';
+import { createClient } from '@/lib/supabase/server';
+import {
+  getDataset,
+  updateDatasetNotes,
+} from '@/lib/supabase/queries/datasets';
+
+export async function GET(request: NextRequest) {
+  const supabase = await createClient();
+
+  const {
+    data: { user },
+  } = await supabase.auth.getUser();
+  if (!user) {
+    return NextResponse.json({ error: 'Unauthorized' }, { status: 401 });
+  }
+
+  const datasetId = new URL(request.url).searchParams.get('dataset_id');
+  if (!datasetId) {
+    return NextResponse.json(
+      { error: 'Missing dataset_id' },
+      { status: 400 },
+    );
+  }
+
+  const dataset = await getDataset(supabase, datasetId);
+  return NextResponse.json({ notes: dataset.notes ?? null });
+}
+
+export async function POST(request: NextRequest) {
+  const supabase = await createClient();
+
+  const {
+    data: { user },
+  } = await supabase.auth.getUser();
+  if (!user) {
+    return NextResponse.json({ error: 'Unauthorized' }, { status: 401 });
+  }
+
+  const { dataset_id, notes } = await request.json();
+  if (!dataset_id) {
+    return NextResponse.json(
+      { error: 'Missing dataset_id' },
+      { status: 400 },
+    );
+  }
+  if (notes !== null && typeof notes !== 'string') {
+    return NextResponse.json(
+      { error: 'notes must be a string or null' },
+      { status: 400 },
+    );
+  }
+
+  // Blank notes are stored as none
+  const value = notes?.trim() ? notes : null;
+  await updateDatasetNotes(supabase, dataset_id, value);
+  return NextResponse.json({ notes: value });
+}
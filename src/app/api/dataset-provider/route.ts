@@ -0,0 +1,34 @@
+import { NextRequest, NextResponse } from 'next/server';
+import { createClient } from '@/lib/supabase/server';
+import { setDatasetDefaultProvider } from '@/lib/supabase/queries/datasets';
+import type { Provider } from '@/lib/types/domain';
+
+const PROVIDERS: Provider[] = ['openai', 'mercury'];
+
+export async function POST(request: NextRequest) {
+  const supabase = await createClient();
+
+  const {
+    data: { user },
+  } = await supabase.auth.getUser();
+  if (!user) {
+    return NextResponse.json({ error: 'Unauthorized' }, { status: 401 });
+  }
+
+  const { dataset_id, provider } = await request.json();
+  if (!dataset_id) {
+    return NextResponse.json(
+      { error: 'Missing dataset_id' },
+      { status: 400 },
+    );
+  }
+  if (provider !== null && !PROVIDERS.includes(provider)) {
+    return NextResponse.json(
+      { error: `provider must be one of: ${PROVIDERS.join(', ')}` },
+      { status: 400 },
+    );
+  }
+
+  await setDatasetDefaultProvider(supabase, dataset_id, provider);
+  return NextResponse.json({ default_provider: provider });
+}
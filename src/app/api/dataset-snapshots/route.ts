@@ -0,0 +1,59 @@
+import { NextRequest, NextResponse } from 'next/server';
+import { createClient } from '@/lib/supabase/server';
+import {
+  getDatasetSnapshots,
+  snapshotDataset,
+} from '@/lib/supabase/queries/dataset-snapshots';
+
+export async function GET(request: NextRequest) {
+  const supabase = await createClient();
+
+  const {
+    data: { user },
+  } = await supabase.auth.getUser();
+  if (!user) {
+    return NextResponse.json({ error: 'Unauthorized' }, { status: 401 });
+  }
+
+  const { searchParams } = new URL(request.url);
+  const datasetId = searchParams.get('dataset_id');
+
+  if (!datasetId) {
+    return NextResponse.json(
+      { error: 'Missing dataset_id' },
+      { status: 400 },
+    );
+  }
+
+  const snapshots = await getDatasetSnapshots(supabase, datasetId);
+  return NextResponse.json({ snapshots });
+}
+
+export async function POST(request: NextRequest) {
+  const supabase = await createClient();
+
+  const {
+    data: { user },
+  } = await supabase.auth.getUser();
+  if (!user) {
+    return NextResponse.json({ error: 'Unauthorized' }, { status: 401 });
+  }
+
+  const { dataset_id, label } = await request.json();
+  if (!dataset_id) {
+    return NextResponse.json(
+      { error: 'Missing dataset_id' },
+      { status: 400 },
+    );
+  }
+
+  try {
+    const snapshotId = await snapshotDataset(supabase, dataset_id, label);
+    return NextResponse.json({ snapshot_id: snapshotId });
+  } catch (err) {
+    return NextResponse.json(
+      { error: err instanceof Error ? err.message : String(err) },
+      { status: 500 },
+    );
+  }
+}
@@ -0,0 +1,90 @@
+import { NextRequest, NextResponse } from 'next/server';
+import OpenAI from 'openai';
+import { createClient } from '@/lib/supabase/server';
+import { randomUUID } from 'crypto';
+import {
+  buildDefaultHeaders,
+  createOpenAIClient,
+  idempotencyHeaders,
+  parseExtraHeaders,
+} from '@/lib/openai/client';
+import { buildChatParams } from '@/lib/openai/text-generation';
+import { DEFAULT_MODEL, MERCURY_BASE_URL } from '@/lib/types/domain';
+import type { Provider } from '@/lib/types/domain';
+
+/**
+ * Sends a single chat completion and returns the exact request body, the
+ * headers the app sets (the SDK's own headers are left out) and the raw
+ * provider response, without touching any dataset.
+ */
+export async function POST(request: NextRequest) {
+  const apiKey = request.headers.get('x-api-key') || request.headers.get('x-openai-api-key');
+  if (!apiKey) {
+    return NextResponse.json({ error: 'Missing API key' }, { status: 401 });
+  }
+  const provider = (request.headers.get('x-ai-provider') || 'openai') as Provider;
+  const baseURL = provider === 'mercury' ? MERCURY_BASE_URL : undefined;
+
+  let extraHeaders: Record<string, string> | undefined;
+  try {
+    extraHeaders = parseExtraHeaders(request.headers.get('x-ai-extra-headers'));
+  } catch (err) {
+    return NextResponse.json(
+      { error: err instanceof Error ? err.message : String(err) },
+      { status: 400 },
+    );
+  }
+
+  const supabase = await createClient();
+  const {
+    data: { user },
+  } = await supabase.auth.getUser();
+  if (!user) {
+    return NextResponse.json({ error: 'Unauthorized' }, { status: 401 });
+  }
+
+  const { prompt, model = DEFAULT_MODEL } = await request.json();
+  if (!prompt) {
+    return NextResponse.json({ error: 'Missing prompt' }, { status: 400 });
+  }
+
+  const openai = createOpenAIClient(apiKey, baseURL, extraHeaders);
+  const params = buildChatParams([{ role: 'user', content: prompt }], model);
+
+  const requestHeaders = idempotencyHeaders(`debug:${randomUUID()}`, 1);
+
+  const sent = {
+    url: `${openai.baseURL}/chat/completions`,
+    headers: {
+      'Content-Type': 'application/json',
+      Authorization: 'Bearer [redacted]',
+      ...buildDefaultHeaders(extraHeaders),
+      ...requestHeaders,
+    },
+    body: params,
+  };
+
+  try {
+    const raw = await openai.chat.completions
+      .create(params, { maxRetries: 0, headers: requestHeaders })
+      .asResponse();
+    return NextResponse.json({
+      request: sent,
+      response: { status: raw.status, body: await raw.text() },
+    });
+  } catch (err) {
+    if (err instanceof OpenAI.APIError) {
+      return NextResponse.json({
+        request: sent,
+        response: {
+          status: err.status ?? null,
+          body: err.error ? JSON.stringify(err.error) : err.message,
+        },
+      });
+    }
+    return NextResponse.json({
+      request: sent,
+      error: err instanceof Error ? err.message : String(err),
+    });
+  }
+}
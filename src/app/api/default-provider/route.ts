@@ -0,0 +1,49 @@
+import { NextRequest, NextResponse } from 'next/server';
+import { createClient } from '@/lib/supabase/server';
+import {
+  getUserSettings,
+  upsertUserSettings,
+} from '@/lib/supabase/queries/user-settings';
+import type { Provider } from '@/lib/types/domain';
+
+const PROVIDERS: Provider[] = ['openai', 'mercury'];
+
+export async function GET() {
+  const supabase = await createClient();
+
+  const {
+    data: { user },
+  } = await supabase.auth.getUser();
+  if (!user) {
+    return NextResponse.json({ error: 'Unauthorized' }, { status: 401 });
+  }
+
+  const settings = await getUserSettings(supabase, user.id);
+  return NextResponse.json({
+    default_provider: settings?.default_provider ?? null,
+  });
+}
+
+export async function POST(request: NextRequest) {
+  const supabase = await createClient();
+
+  const {
+    data: { user },
+  } = await supabase.auth.getUser();
+  if (!user) {
+    return NextResponse.json({ error: 'Unauthorized' }, { status: 401 });
+  }
+
+  const { provider } = await request.json();
+  if (provider !== null && !PROVIDERS.includes(provider)) {
+    return NextResponse.json(
+      { error: `provider must be one of: ${PROVIDERS.join(', ')}` },
+      { status: 400 },
+    );
+  }
+
+  const settings = await upsertUserSettings(supabase, user.id, {
+    default_provider: provider,
+  });
+  return NextResponse.json({ default_provider: settings.default_provider });
+}
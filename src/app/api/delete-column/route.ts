@@ -0,0 +1,41 @@
+import { NextRequest, NextResponse } from 'next/server';
+import { createClient } from '@/lib/supabase/server';
+import {
+  deleteColumn,
+  getColumnDependents,
+} from '@/lib/supabase/queries/columns';
+
+/**
+ * Deletes a column, reporting the columns whose prompts reference it. With
+ * `dry_run` nothing is deleted, so the caller can warn before breaking them.
+ */
+export async function POST(request: NextRequest) {
+  const supabase = await createClient();
+  const {
+    data: { user },
+  } = await supabase.auth.getUser();
+
+  if (!user) {
+    return NextResponse.json({ error: 'Unauthorized' }, { status: 401 });
+  }
+
+  const { column_id, dry_run = false } = await request.json();
+  if (!column_id) {
+    return NextResponse.json({ error: 'Missing column_id' }, { status: 400 });
+  }
+
+  try {
+    const dependents = await getColumnDependents(supabase, column_id);
+    if (dry_run) {
+      return NextResponse.json({ deleted: false, dependents });
+    }
+
+    await deleteColumn(supabase, column_id);
+    return NextResponse.json({ deleted: true, dependents });
+  } catch (err) {
+    return NextResponse.json(
+      { error: err instanceof Error ? err.message : String(err) },
+      { status: 500 },
+    );
+  }
+}
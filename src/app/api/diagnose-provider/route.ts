@@ -0,0 +1,152 @@
+import { NextRequest, NextResponse } from 'next/server';
+import { createClient } from '@/lib/supabase/server';
+import { createOpenAIClient, parseExtraHeaders } from '@/lib/openai/client';
+import { classifyProviderError } from '@/lib/openai/errors';
+import { checkModelAccess } from '@/lib/openai/model-access';
+import { DEFAULT_MODEL, MERCURY_BASE_URL } from '@/lib/types/domain';
+import type { Provider } from '@/lib/types/domain';
+
+const OPENAI_BASE_URL = 'https://api.openai.com/v1';
+const STEP_TIMEOUT_MS = 15_000;
+
+type StepName = 'connectivity' | 'auth' | 'model_access' | 'sample';
+
+interface StepResult {
+  step: StepName;
+  status: 'passed' | 'failed' | 'skipped';
+  duration_ms: number;
+  message?: string;
+}
+
+/**
+ * Runs one step with its own timeout. A step returns a message on success or
+ * throws to fail.
+ */
+async function runStep(
+  step: StepName,
+  check: () => Promise<string | void>,
+): Promise<StepResult> {
+  const startedAt = performance.now();
+  let timer: ReturnType<typeof setTimeout> | undefined;
+  const timeout = new Promise<never>((_, reject) => {
+    timer = setTimeout(
+      () => reject(new Error(`Timed out after ${STEP_TIMEOUT_MS / 1000}s`)),
+      STEP_TIMEOUT_MS,
+    );
+  });
+
+  try {
+    const message = await Promise.race([check(), timeout]);
+    return {
+      step,
+      status: 'passed',
+      duration_ms: Math.round(performance.now() - startedAt),
+      ...(message && { message }),
+    };
+  } catch (err) {
+    return {
+      step,
+      status: 'failed',
+      duration_ms: Math.round(performance.now() - startedAt),
+      message: err instanceof Error ? err.message : String(err),
+    };
+  } finally {
+    clearTimeout(timer);
+  }
+}
+
+/**
+ * Tests a provider setup step by step: reaching the API, the key, access to
+ * the model and a tiny generation. Later steps are skipped once one fails, so
+ * the report points at the first thing that's wrong.
+ */
+export async function GET(request: NextRequest) {
+  const supabase = await createClient();
+
+  const {
+    data: { user },
+  } = await supabase.auth.getUser();
+  if (!user) {
+    return NextResponse.json({ error: 'Unauthorized' }, { status: 401 });
+  }
+
+  const apiKey = request.headers.get('x-api-key') || request.headers.get('x-openai-api-key');
+  if (!apiKey) {
+    return NextResponse.json({ error: 'Missing API key' }, { status: 401 });
+  }
+
+  let extraHeaders: Record<string, string> | undefined;
+  try {
+    extraHeaders = parseExtraHeaders(request.headers.get('x-ai-extra-headers'));
+  } catch (err) {
+    return NextResponse.json(
+      { error: err instanceof Error ? err.message : String(err) },
+      { status: 400 },
+    );
+  }
+
+  const model = new URL(request.url).searchParams.get('model') || DEFAULT_MODEL;
+  const provider = (request.headers.get('x-ai-provider') || 'openai') as Provider;
+  const baseURL = provider === 'mercury' ? MERCURY_BASE_URL : undefined;
+  const client = createOpenAIClient(apiKey, baseURL, extraHeaders);
+
+  const checks: Array<[StepName, () => Promise<string | void>]> = [
+    [
+      'connectivity',
+      async () => {
+        // Any HTTP response at all means the API is reachable
+        const res = await fetch(`${baseURL ?? OPENAI_BASE_URL}/models`);
+        return `HTTP ${res.status}`;
+      },
+    ],
+    [
+      'auth',
+      async () => {
+        try {
+          await client.models.list({ maxRetries: 0 });
+        } catch (err) {
+          const { kind, message } = classifyProviderError(err);
+          if (kind === 'auth') throw new Error(message);
+          return 'Model listing unavailable; key checked in the next steps';
+        }
+      },
+    ],
+    [
+      'model_access',
+      async () => {
+        const { status, message } = await checkModelAccess(client, model);
+        if (status !== 'accessible') throw new Error(message || status);
+      },
+    ],
+    [
+      'sample',
+      async () => {
+        const completion = await client.chat.completions.create(
+          {
+            model,
+            messages: [{ role: 'user', content: 'Reply with OK.' }],
+            max_tokens: 5,
+          },
+          { maxRetries: 0 },
+        );
+        return completion.choices[0]?.message?.content?.trim() || '';
+      },
+    ],
+  ];
+
+  const steps: StepResult[] = [];
+  for (const [step, check] of checks) {
+    if (steps.some((s) => s.status === 'failed')) {
+      steps.push({ step, status: 'skipped', duration_ms: 0 });
+      continue;
+    }
+    steps.push(await runStep(step, check));
+  }
+
+  return NextResponse.json({
+    provider,
+    model,
+    ok: steps.every((s) => s.status === 'passed'),
+    steps,
+  });
+}
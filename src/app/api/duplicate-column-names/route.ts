@@ -0,0 +1,70 @@
+import { NextRequest, NextResponse } from 'next/server';
+import { createClient } from '@/lib/supabase/server';
+import {
+  findDuplicateColumns,
+  resolveDuplicateColumns,
+} from '@/lib/supabase/queries/columns';
+
+/**
+ * Lists groups of columns that share a name within a dataset
+ */
+export async function GET(request: NextRequest) {
+  const supabase = await createClient();
+
+  const {
+    data: { user },
+  } = await supabase.auth.getUser();
+  if (!user) {
+    return NextResponse.json({ error: 'Unauthorized' }, { status: 401 });
+  }
+
+  const datasetId = new URL(request.url).searchParams.get('dataset_id');
+  if (!datasetId) {
+    return NextResponse.json(
+      { error: 'Missing dataset_id' },
+      { status: 400 },
+    );
+  }
+
+  try {
+    const groups = await findDuplicateColumns(supabase, datasetId);
+    return NextResponse.json({ groups });
+  } catch (err) {
+    return NextResponse.json(
+      { error: err instanceof Error ? err.message : String(err) },
+      { status: 500 },
+    );
+  }
+}
+
+/**
+ * Renames every duplicate but the first in each group with a numeric suffix
+ */
+export async function POST(request: NextRequest) {
+  const supabase = await createClient();
+
+  const {
+    data: { user },
+  } = await supabase.auth.getUser();
+  if (!user) {
+    return NextResponse.json({ error: 'Unauthorized' }, { status: 401 });
+  }
+
+  const { dataset_id } = await request.json();
+  if (!dataset_id) {
+    return NextResponse.json(
+      { error: 'Missing dataset_id' },
+      { status: 400 },
+    );
+  }
+
+  try {
+    const renamed = await resolveDuplicateColumns(supabase, dataset_id);
+    return NextResponse.json({ renamed });
+  } catch (err) {
+    return NextResponse.json(
+      { error: err instanceof Error ? err.message : String(err) },
+      { status: 500 },
+    );
+  }
+}
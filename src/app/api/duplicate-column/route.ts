@@ -0,0 +1,35 @@
+import { NextRequest, NextResponse } from 'next/server';
+import { createClient } from '@/lib/supabase/server';
+import { duplicateColumn, getColumn } from '@/lib/supabase/queries/columns';
+
+export async function POST(request: NextRequest) {
+  const supabase = await createClient();
+  const {
+    data: { user },
+  } = await supabase.auth.getUser();
+
+  if (!user) {
+    return NextResponse.json({ error: 'Unauthorized' }, { status: 401 });
+  }
+
+  const { column_id, copy_cells = false, new_name } = await request.json();
+  if (!column_id) {
+    return NextResponse.json({ error: 'Missing column_id' }, { status: 400 });
+  }
+
+  try {
+    const newColumnId = await duplicateColumn(
+      supabase,
+      column_id,
+      copy_cells,
+      new_name?.trim(),
+    );
+    const column = await getColumn(supabase, newColumnId);
+    return NextResponse.json({ column });
+  } catch (err) {
+    return NextResponse.json(
+      { error: err instanceof Error ? err.message : String(err) },
+      { status: 500 },
+    );
+  }
+}
@@ -0,0 +1,54 @@
+import { NextRequest, NextResponse } from 'next/server';
+import { createClient } from '@/lib/supabase/server';
+import { getUserSettings } from '@/lib/supabase/queries/user-settings';
+import { MERCURY_BASE_URL } from '@/lib/types/domain';
+
+const redact = (value: string | null | undefined) =>
+  value ? `${value.slice(0, 4)}…(${value.length} chars)` : null;
+
+/**
+ * Reports the configuration this server process and request actually see,
+ * with secrets redacted, so users can confirm saved settings took effect.
+ */
+export async function GET(request: NextRequest) {
+  const supabase = await createClient();
+
+  const {
+    data: { user },
+  } = await supabase.auth.getUser();
+  if (!user) {
+    return NextResponse.json({ error: 'Unauthorized' }, { status: 401 });
+  }
+
+  const settings = await getUserSettings(supabase, user.id);
+  const apiKey =
+    request.headers.get('x-api-key') || request.headers.get('x-openai-api-key');
+  const extraHeaders = request.headers.get('x-ai-extra-headers');
+  let extraHeaderNames: string[] = [];
+  try {
+    extraHeaderNames = extraHeaders ? Object.keys(JSON.parse(extraHeaders)) : [];
+  } catch {
+    // Reported as-is below; generation routes reject it with a 400
+  }
+
+  return NextResponse.json({
+    env: {
+      NEXT_PUBLIC_SUPABASE_URL: process.env.NEXT_PUBLIC_SUPABASE_URL ?? null,
+      NEXT_PUBLIC_SUPABASE_ANON_KEY: redact(
+        process.env.NEXT_PUBLIC_SUPABASE_ANON_KEY,
+      ),
+      GLOBAL_SYSTEM_PREFIX: process.env.GLOBAL_SYSTEM_PREFIX || null,
+    },
+    settings: {
+      default_provider: settings?.default_provider ?? null,
+      store_prompts: settings?.store_prompts ?? false,
+    },
+    request: {
+      provider: request.headers.get('x-ai-provider'),
+      mercury_base_url: MERCURY_BASE_URL,
+      api_key: redact(apiKey),
+      // Header values may carry credentials, so only names are reported
+      extra_headers: extraHeaders ? extraHeaderNames : null,
+    },
+  });
+}
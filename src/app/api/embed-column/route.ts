@@ -0,0 +1,183 @@
+import { NextRequest } from 'next/server';
+import { createClient } from '@/lib/supabase/server';
+import { createProgressStream } from '@/lib/utils/progress-stream';
+import { createOpenAIClient, parseExtraHeaders } from '@/lib/openai/client';
+import { embedTexts } from '@/lib/openai/embeddings';
+import { getColumnCellValues } from '@/lib/supabase/queries/cells';
+import {
+  deleteEmbeddingsByIds,
+  getEmbeddingIdsBySourcePrefix,
+  insertEmbeddings,
+} from '@/lib/supabase/queries/embeddings';
+import { EMBEDDING_BATCH_SIZES, MERCURY_BASE_URL } from '@/lib/types/domain';
+import type { Provider } from '@/lib/types/domain';
+
+export const maxDuration = 300;
+
+const BATCH_ATTEMPTS = 2;
+const PROVIDERS: Provider[] = ['openai', 'mercury'];
+
+/**
+ * Embeds every non-empty value of a column in provider-sized batches. A batch
+ * that still fails after retrying is reported and skipped so the rest of the
+ * column is embedded.
+ */
+export async function POST(request: NextRequest) {
+  const apiKey = request.headers.get('x-api-key') || request.headers.get('x-openai-api-key');
+  if (!apiKey) {
+    return new Response(JSON.stringify({ error: 'Missing API key' }), {
+      status: 401,
+    });
+  }
+  const provider = (request.headers.get('x-ai-provider') || 'openai') as Provider;
+  if (!PROVIDERS.includes(provider)) {
+    return new Response(
+      JSON.stringify({
+        error: `x-ai-provider must be one of: ${PROVIDERS.join(', ')}`,
+      }),
+      { status: 400 },
+    );
+  }
+  const baseURL = provider === 'mercury' ? MERCURY_BASE_URL : undefined;
+
+  let extraHeaders: Record<string, string> | undefined;
+  try {
+    extraHeaders = parseExtraHeaders(request.headers.get('x-ai-extra-headers'));
+  } catch (err) {
+    return new Response(
+      JSON.stringify({ error: err instanceof Error ? err.message : String(err) }),
+      { status: 400 },
+    );
+  }
+
+  const {
+    dataset_id,
+    column_id,
+    model,
+    batch_size,
+  } = await request.json();
+
+  if (!dataset_id || !column_id) {
+    return new Response(
+      JSON.stringify({ error: 'Missing dataset_id or column_id' }),
+      { status: 400 },
+    );
+  }
+  if (
+    batch_size !== undefined &&
+    (!Number.isInteger(batch_size) || batch_size <= 0)
+  ) {
+    return new Response(
+      JSON.stringify({ error: 'batch_size must be a positive integer' }),
+      { status: 400 },
+    );
+  }
+  // Never above what the provider accepts in one request
+  const batchSize = Math.min(
+    batch_size ?? EMBEDDING_BATCH_SIZES[provider],
+    EMBEDDING_BATCH_SIZES[provider],
+  );
+
+  const supabase = await createClient();
+  const {
+    data: { user },
+  } = await supabase.auth.getUser();
+  if (!user) {
+    return new Response(JSON.stringify({ error: 'Unauthorized' }), {
+      status: 401,
+    });
+  }
+
+  const openai = createOpenAIClient(apiKey, baseURL, extraHeaders);
+
+  return createProgressStream(async ({ send, signal }) => {
+    try {
+      const sourcePrefix = `column:${column_id}:`;
+      const cells = (await getColumnCellValues(supabase, column_id)).filter(
+        (cell) => cell.value != null && String(cell.value).trim() !== '',
+      );
+      const progress = { total: cells.length, completed: 0, failed: 0 };
+
+      // Re-embedding replaces the column's previous embeddings. Old ones are
+      // only removed once their row's new embedding is written, so a cancel
+      // or failure never leaves a row without one.
+      const previousIds = new Map<string, string[]>();
+      for (const row of await getEmbeddingIdsBySourcePrefix(
+        supabase,
+        dataset_id,
+        sourcePrefix,
+      )) {
+        previousIds.set(row.source_uri, [
+          ...(previousIds.get(row.source_uri) ?? []),
+          row.id,
+        ]);
+      }
+      const replacePrevious = async (sourceUris: string[]) => {
+        await deleteEmbeddingsByIds(
+          supabase,
+          sourceUris.flatMap((uri) => previousIds.get(uri) ?? []),
+        );
+        for (const uri of sourceUris) previousIds.delete(uri);
+      };
+
+      for (let i = 0; i < cells.length && !signal.aborted; i += batchSize) {
+        const batch = cells.slice(i, i + batchSize);
+        const texts = batch.map((cell) => String(cell.value));
+
+        let lastError = '';
+        let embedded = false;
+        for (let attempt = 1; attempt <= BATCH_ATTEMPTS && !embedded; attempt++) {
+          try {
+            const embeddings = await embedTexts(openai, texts, model);
+            await insertEmbeddings(
+              supabase,
+              batch.map((cell, j) => ({
+                dataset_id,
+                source_uri: `${sourcePrefix}${cell.row_idx}`,
+                text: texts[j],
+                embedding: embeddings[j],
+              })),
+            );
+            embedded = true;
+          } catch (err) {
+            lastError = err instanceof Error ? err.message : String(err);
+          }
+        }
+
+        if (embedded) {
+          await replacePrevious(
+            batch.map((cell) => `${sourcePrefix}${cell.row_idx}`),
+          );
+          progress.completed += batch.length;
+        } else {
+          progress.failed += batch.length;
+          send('embed.batch.error', {
+            from_row: batch[0].row_idx,
+            to_row: batch[batch.length - 1].row_idx,
+            error: lastError,
+          });
+        }
+        send('embed.progress', { column_id, progress });
+      }
+
+      // Rows that are empty now keep no embedding
+      if (!signal.aborted) {
+        const embeddedUris = new Set(
+          cells.map((cell) => `${sourcePrefix}${cell.row_idx}`),
+        );
+        await replacePrevious(
+          [...previousIds.keys()].filter((uri) => !embeddedUris.has(uri)),
+        );
+      }
+
+      send(signal.aborted ? 'embed.aborted' : 'embed.complete', {
+        column_id,
+        progress,
+      });
+    } catch (err) {
+      send('embed.error', {
+        error: err instanceof Error ? err.message : String(err),
+      });
+    }
+  }, user.id);
+}
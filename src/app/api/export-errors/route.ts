@@ -0,0 +1,54 @@
+import { NextRequest, NextResponse } from 'next/server';
+import { createClient } from '@/lib/supabase/server';
+import { getDatasetErrors } from '@/lib/supabase/queries/cell-meta';
+import Papa from 'papaparse';
+
+export async function GET(request: NextRequest) {
+  const supabase = await createClient();
+
+  const {
+    data: { user },
+  } = await supabase.auth.getUser();
+  if (!user) {
+    return NextResponse.json({ error: 'Unauthorized' }, { status: 401 });
+  }
+
+  const { searchParams } = new URL(request.url);
+  const datasetId = searchParams.get('dataset_id');
+  const format = searchParams.get('format') || 'csv';
+
+  if (!datasetId) {
+    return NextResponse.json(
+      { error: 'Missing dataset_id' },
+      { status: 400 },
+    );
+  }
+
+  try {
+    const errors = await getDatasetErrors(supabase, datasetId);
+
+    if (format === 'json') {
+      return new Response(JSON.stringify(errors, null, 2), {
+        headers: {
+          'Content-Type': 'application/json',
+          'Content-Disposition': 'attachment; filename="errors.json"',
+        },
+      });
+    }
+
+    const csv = Papa.unparse(errors, {
+      columns: ['column', 'row_idx', 'status', 'error'],
+    });
+    return new Response(csv, {
+      headers: {
+        'Content-Type': 'text/csv',
+        'Content-Disposition': 'attachment; filename="errors.csv"',
+      },
+    });
+  } catch (err) {
+    return NextResponse.json(
+      { error: err instanceof Error ? err.message : String(err) },
+      { status: 500 },
+    );
+  }
+}
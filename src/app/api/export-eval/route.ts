@@ -0,0 +1,111 @@
+import { NextRequest, NextResponse } from 'next/server';
+import { createClient } from '@/lib/supabase/server';
+import { getDatasetColumns } from '@/lib/supabase/queries/columns';
+import { getColumnCellValues } from '@/lib/supabase/queries/cells';
+import { getProcess } from '@/lib/supabase/queries/processes';
+import { renderInstruction } from '@/lib/utils/prompt-template';
+
+const isMissing = (value: any) =>
+  value === null || value === undefined || value === '';
+
+/**
+ * Exports `{prompt, expected, actual}` lines for external eval tooling. The
+ * prompt is the output column's instruction rendered with each row's input
+ * values. Rows missing an expected or actual value are kept and flagged.
+ */
+export async function GET(request: NextRequest) {
+  const supabase = await createClient();
+
+  const {
+    data: { user },
+  } = await supabase.auth.getUser();
+  if (!user) {
+    return NextResponse.json({ error: 'Unauthorized' }, { status: 401 });
+  }
+
+  const { searchParams } = new URL(request.url);
+  const datasetId = searchParams.get('dataset_id');
+  const expectedColumnId = searchParams.get('expected_column_id');
+  const outputColumnId = searchParams.get('output_column_id');
+  // Defaults to the columns the output column's prompt references
+  const inputParam = searchParams.get('input_column_ids');
+
+  if (!datasetId || !expectedColumnId || !outputColumnId) {
+    return NextResponse.json(
+      { error: 'Missing dataset_id, expected_column_id or output_column_id' },
+      { status: 400 },
+    );
+  }
+
+  try {
+    const process = await getProcess(supabase, outputColumnId);
+    if (!process) {
+      return NextResponse.json(
+        { error: 'Output column has no prompt to materialize' },
+        { status: 400 },
+      );
+    }
+
+    const columns = await getDatasetColumns(supabase, datasetId);
+    const names = new Map<string, string>(
+      columns.map((c: any) => [c.id, c.name]),
+    );
+    const inputIds: string[] = inputParam
+      ? inputParam.split(',').map((id) => id.trim())
+      : process.process_columns.map((pc: { column_id: string }) => pc.column_id);
+
+    const unknown = [...inputIds, expectedColumnId, outputColumnId].filter(
+      (id) => !names.has(id),
+    );
+    if (unknown.length > 0) {
+      return NextResponse.json(
+        { error: `Unknown columns: ${unknown.join(', ')}` },
+        { status: 400 },
+      );
+    }
+
+    const valuesByColumn = new Map<string, Map<number, any>>();
+    for (const id of new Set([...inputIds, expectedColumnId, outputColumnId])) {
+      const cells = await getColumnCellValues(supabase, id);
+      valuesByColumn.set(id, new Map(cells.map((c) => [c.row_idx, c.value])));
+    }
+
+    const rowIndices = Array.from(
+      new Set(
+        Array.from(valuesByColumn.values()).flatMap((m) => Array.from(m.keys())),
+      ),
+    ).sort((a, b) => a - b);
+
+    const lines = rowIndices.map((rowIdx) => {
+      const data = Object.fromEntries(
+        inputIds.map((id) => [names.get(id)!, valuesByColumn.get(id)!.get(rowIdx)]),
+      );
+      const expected = valuesByColumn.get(expectedColumnId)!.get(rowIdx) ?? null;
+      const actual = valuesByColumn.get(outputColumnId)!.get(rowIdx) ?? null;
+
+      const missing = [
+        ...(isMissing(expected) ? ['expected'] : []),
+        ...(isMissing(actual) ? ['actual'] : []),
+      ];
+      return JSON.stringify({
+        row_idx: rowIdx,
+        prompt: renderInstruction(process.prompt, data),
+        expected,
+        actual,
+        ...(missing.length > 0 && { missing }),
+      });
+    });
+
+    return new Response(lines.join('\n') + (lines.length ? '\n' : ''), {
+      headers: {
+        'Content-Type': 'application/x-ndjson',
+        'Content-Disposition': 'attachment; filename="eval.jsonl"',
+      },
+    });
+  } catch (err) {
+    return NextResponse.json(
+      { error: err instanceof Error ? err.message : String(err) },
+      { status: 500 },
+    );
+  }
+}
@@ -1,6 +1,16 @@
 import { NextRequest, NextResponse } from 'next/server';
 import { createClient } from '@/lib/supabase/server';
 import Papa from 'papaparse';
+import { toSqliteDump } from '@/lib/utils/sqlite-dump';
+import { trackOperation } from '@/lib/utils/progress-stream';
+import {
+  createRowRedactor,
+  REDACT_MODES,
+  type RedactMode,
+} from '@/lib/utils/redact';
+
+// PostgREST caps each response at 1000 rows
+const PAGE_SIZE = 1000;
 
 export async function GET(request: NextRequest) {
   const supabase = await createClient();
@@ -15,6 +25,15 @@ export async function GET(request: NextRequest) {
   const { searchParams } = new URL(request.url);
   const datasetId = searchParams.get('dataset_id');
   const format = searchParams.get('format') || 'csv';
+  const delimiter = searchParams.get('delimiter') || ',';
+  const quoteStyle = searchParams.get('quote') || 'minimal';
+  // Papa.unparse defaults to CRLF line endings
+  const terminator = searchParams.get('newline') === 'lf' ? '\n' : '\r\n';
+  // Optional comma-separated row indices to export a selection
+  const rowsParam = searchParams.get('rows');
+  // Optional comma-separated column ids to redact, and how
+  const redactParam = searchParams.get('redact');
+  const redactMode = (searchParams.get('redact_mode') || 'mask') as RedactMode;
 
   if (!datasetId) {
     return NextResponse.json(
@@ -23,8 +42,33 @@ export async function GET(request: NextRequest) {
     );
   }
 
+  if (quoteStyle !== 'minimal' && quoteStyle !== 'all') {
+    return NextResponse.json(
+      { error: 'quote must be "minimal" or "all"' },
+      { status: 400 },
+    );
+  }
+
+  let rowIndices: number[] | null = null;
+  if (rowsParam) {
+    rowIndices = rowsParam.split(',').map((r) => Number(r.trim()));
+    if (rowIndices.some((r) => !Number.isInteger(r) || r < 0)) {
+      return NextResponse.json(
+        { error: 'rows must be a comma-separated list of row indices' },
+        { status: 400 },
+      );
+    }
+  }
+
+  if (!REDACT_MODES.includes(redactMode)) {
+    return NextResponse.json(
+      { error: 'redact_mode must be "hash", "mask" or "drop"' },
+      { status: 400 },
+    );
+  }
+
   // Get columns
-  const { data: columns, error: colError } = await supabase
+  const { data: allColumns, error: colError } = await supabase
     .from('columns')
     .select('id, name')
     .eq('dataset_id', datasetId)
@@ -33,26 +77,75 @@ export async function GET(request: NextRequest) {
   if (colError) {
     return NextResponse.json({ error: colError.message }, { status: 500 });
   }
-  if (!columns || columns.length === 0) {
+  if (!allColumns || allColumns.length === 0) {
     return NextResponse.json({ error: 'No columns found' }, { status: 404 });
   }
 
-  // Get all cell values
-  const { data: cells, error: cellError } = await supabase
-    .from('cell_values')
-    .select('column_id, row_idx, value')
-    .eq('dataset_id', datasetId)
-    .order('row_idx', { ascending: true });
+  const redactIds = new Set(
+    (redactParam || '').split(',').map((id) => id.trim()).filter(Boolean),
+  );
+  // Hashes are salted per user so they can't be reversed by a dictionary
+  // built elsewhere, yet stay stable across that user's exports
+  const redactRow = createRowRedactor(
+    allColumns.filter((c: any) => redactIds.has(c.id)).map((c: any) => c.name),
+    redactMode,
+    process.env.EXPORT_REDACTION_SALT || user.id,
+  );
+  const columns =
+    redactMode === 'drop'
+      ? allColumns.filter((c: any) => !redactIds.has(c.id))
+      : allColumns;
 
-  if (cellError) {
-    return NextResponse.json({ error: cellError.message }, { status: 500 });
+  if (format === 'ndjson') {
+    // Cancellable through DELETE /api/operations?id=<X-Operation-Id>
+    const { operation, signal, finish } = trackOperation(user.id);
+    return new Response(
+      streamNdjson(
+        supabase,
+        datasetId,
+        columns,
+        rowIndices,
+        redactRow,
+        signal,
+        finish,
+      ),
+      {
+        headers: {
+          'Content-Type': 'application/x-ndjson',
+          'Content-Disposition': 'attachment; filename="export.ndjson"',
+          'X-Operation-Id': operation.id,
+        },
+      },
+    );
+  }
+
+  // Get all cell values, restricted to the selected rows if given
+  const cells: Array<{ column_id: string; row_idx: number; value: any }> = [];
+  for (let from = 0; ; from += PAGE_SIZE) {
+    let cellQuery = supabase
+      .from('cell_values')
+      .select('column_id, row_idx, value')
+      .eq('dataset_id', datasetId);
+    if (rowIndices) {
+      cellQuery = cellQuery.in('row_idx', rowIndices);
+    }
+    const { data, error: cellError } = await cellQuery
+      .order('row_idx', { ascending: true })
+      .order('column_id', { ascending: true })
+      .range(from, from + PAGE_SIZE - 1);
+
+    if (cellError) {
+      return NextResponse.json({ error: cellError.message }, { status: 500 });
+    }
+    cells.push(...data);
+    if (data.length < PAGE_SIZE) break;
   }
 
   // Build row map
   const columnMap = new Map<string, string>(columns.map((c: any) => [c.id, c.name]));
   const rowMap = new Map<number, Record<string, any>>();
 
-  for (const cell of cells || []) {
+  for (const cell of cells) {
     if (!rowMap.has(cell.row_idx)) {
       rowMap.set(cell.row_idx, {});
     }
@@ -64,7 +157,7 @@ export async function GET(request: NextRequest) {
 
   const rows = Array.from(rowMap.entries())
     .sort(([a], [b]) => a - b)
-    .map(([, row]) => row);
+    .map(([, row]) => redactRow(row));
 
   if (format === 'json') {
     return new Response(JSON.stringify(rows, null, 2), {
@@ -75,8 +168,25 @@ export async function GET(request: NextRequest) {
     });
   }
 
+  if (format === 'sqlite') {
+    const sql = toSqliteDump(
+      'dataset',
+      columns.map((c: any) => c.name),
+      rows,
+    );
+    return new Response(sql, {
+      headers: {
+        'Content-Type': 'application/sql',
+        'Content-Disposition': 'attachment; filename="export.sql"',
+      },
+    });
+  }
+
   const csv = Papa.unparse(rows, {
     columns: columns.map((c: any) => c.name),
+    delimiter,
+    newline: terminator,
+    quotes: quoteStyle === 'all',
   });
 
   return new Response(csv, {
@@ -86,3 +196,88 @@ export async function GET(request: NextRequest) {
     },
   });
 }
+
+/**
+ * Streams one JSON object per row, paging through cell values so the full
+ * dataset is never held in memory. A cancelled export errors the stream
+ * instead of ending it, so the client discards the partial download rather
+ * than keeping a truncated file that looks complete.
+ */
+function streamNdjson(
+  supabase: any,
+  datasetId: string,
+  columns: Array<{ id: string; name: string }>,
+  rowIndices: number[] | null,
+  redactRow: (row: Record<string, any>) => Record<string, any>,
+  signal: AbortSignal,
+  finish: () => void,
+) {
+  const columnMap = new Map(columns.map((c) => [c.id, c.name]));
+  const encoder = new TextEncoder();
+  let from = 0;
+  let currentRowIdx: number | null = null;
+  let currentRow: Record<string, any> = {};
+  let done = false;
+
+  return new ReadableStream({
+    async pull(controller) {
+      if (signal.aborted) {
+        finish();
+        controller.error(new Error('Export cancelled'));
+        return;
+      }
+      if (done) {
+        finish();
+        controller.close();
+        return;
+      }
+
+      let query = supabase
+        .from('cell_values')
+        .select('column_id, row_idx, value')
+        .eq('dataset_id', datasetId);
+      if (rowIndices) {
+        query = query.in('row_idx', rowIndices);
+      }
+      const { data: cells, error } = await query
+        .order('row_idx', { ascending: true })
+        .order('column_id', { ascending: true })
+        .range(from, from + PAGE_SIZE - 1);
+
+      if (error) {
+        finish();
+        controller.error(error);
+        return;
+      }
+      from += cells.length;
+
+      let chunk = '';
+      for (const cell of cells) {
+        if (currentRowIdx !== null && cell.row_idx !== currentRowIdx) {
+          chunk += JSON.stringify(redactRow(currentRow)) + '\n';
+          currentRow = {};
+        }
+        currentRowIdx = cell.row_idx;
+        const colName = columnMap.get(cell.column_id);
+        if (colName) {
+          currentRow[colName] = cell.value;
+        }
+      }
+
+      if (cells.length < PAGE_SIZE) {
+        if (currentRowIdx !== null) {
+          chunk += JSON.stringify(redactRow(currentRow)) + '\n';
+        }
+        done = true;
+      }
+
+      if (chunk) {
+        controller.enqueue(encoder.encode(chunk));
+      }
+    },
+    cancel() {
+      // The client stopped reading
+      finish();
+    },
+  });
+}
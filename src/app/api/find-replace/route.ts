@@ -0,0 +1,72 @@
+import { NextRequest, NextResponse } from 'next/server';
+import { createClient } from '@/lib/supabase/server';
+import {
+  getColumnCellValues,
+  replaceCellValues,
+} from '@/lib/supabase/queries/cells';
+
+const escapeRegExp = (text: string) =>
+  text.replace(/[.*+?^${}()|[\]\\]/g, '\\$&');
+
+export async function POST(request: NextRequest) {
+  const supabase = await createClient();
+  const {
+    data: { user },
+  } = await supabase.auth.getUser();
+
+  if (!user) {
+    return NextResponse.json({ error: 'Unauthorized' }, { status: 401 });
+  }
+
+  const { column_id, find, replace = '', regex = false } = await request.json();
+
+  if (!column_id || !find) {
+    return NextResponse.json(
+      { error: 'Missing column_id or find' },
+      { status: 400 },
+    );
+  }
+
+  // Compile before touching any cell so a bad pattern never partially applies
+  let pattern: RegExp;
+  try {
+    pattern = new RegExp(regex ? find : escapeRegExp(find), 'g');
+  } catch (err) {
+    return NextResponse.json(
+      {
+        error: `Invalid regex: ${err instanceof Error ? err.message : String(err)}`,
+      },
+      { status: 400 },
+    );
+  }
+
+  try {
+    const cells = await getColumnCellValues(supabase, column_id);
+
+    const updates = cells
+      .filter((cell) => typeof cell.value === 'string')
+      .map((cell) => ({
+        row_idx: cell.row_idx,
+        before: cell.value as string,
+        // Literal mode must not expand `$&`-style replacement patterns
+        after: regex
+          ? (cell.value as string).replace(pattern, replace)
+          : (cell.value as string).replace(pattern, () => replace),
+      }))
+      .filter((cell) => cell.after !== cell.before);
+
+    // One transaction, so a failure never leaves the column half-replaced
+    await replaceCellValues(
+      supabase,
+      column_id,
+      updates.map((cell) => ({ row_idx: cell.row_idx, value: cell.after })),
+    );
+
+    return NextResponse.json({ changed: updates.length });
+  } catch (err) {
+    return NextResponse.json(
+      { error: err instanceof Error ? err.message : String(err) },
+      { status: 500 },
+    );
+  }
+}
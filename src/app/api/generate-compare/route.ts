@@ -0,0 +1,249 @@
+import { NextRequest } from 'next/server';
+import { createClient } from '@/lib/supabase/server';
+import {
+  abortOperation,
+  createProgressStream,
+  readProgressEvents,
+} from '@/lib/utils/progress-stream';
+import { duplicateColumn, getColumn } from '@/lib/supabase/queries/columns';
+import { getProcess, upsertProcess } from '@/lib/supabase/queries/processes';
+import type { GenerationProgress, Provider } from '@/lib/types/domain';
+
+export const maxDuration = 300;
+
+const PROVIDERS: Provider[] = ['openai', 'mercury'];
+
+interface CompareTarget {
+  provider: Provider;
+  model: string;
+  // Falls back to the request's x-api-key when the provider is the header's
+  api_key?: string;
+}
+
+/**
+ * Generates the same column with several provider/model pairs side by side.
+ * Each target gets its own copy of the column, named after the provider, with
+ * the same prompt and references so only the model differs.
+ */
+export async function POST(request: NextRequest) {
+  const apiKey = request.headers.get('x-api-key') || request.headers.get('x-openai-api-key');
+  // The provider the request's key belongs to
+  const keyProvider = request.headers.get('x-ai-provider') || 'openai';
+  const rawExtraHeaders = request.headers.get('x-ai-extra-headers');
+
+  const supabase = await createClient();
+  const {
+    data: { user },
+  } = await supabase.auth.getUser();
+  if (!user) {
+    return new Response(JSON.stringify({ error: 'Unauthorized' }), {
+      status: 401,
+    });
+  }
+
+  const {
+    dataset_id,
+    column_id,
+    targets,
+    offset = 0,
+    limit,
+  }: {
+    dataset_id: string;
+    column_id: string;
+    targets: CompareTarget[];
+    offset?: number;
+    limit: number;
+  } = await request.json();
+
+  if (!dataset_id || !column_id || !Array.isArray(targets) || !limit) {
+    return new Response(
+      JSON.stringify({ error: 'Missing dataset_id, column_id, targets or limit' }),
+      { status: 400 },
+    );
+  }
+  if (targets.some((t) => !PROVIDERS.includes(t.provider) || !t.model)) {
+    return new Response(
+      JSON.stringify({
+        error: `Each target needs a provider (${PROVIDERS.join(', ')}) and a model`,
+      }),
+      { status: 400 },
+    );
+  }
+  // The request's key is only sent to its own provider
+  if (!apiKey && targets.some((t) => !t.api_key)) {
+    return new Response(
+      JSON.stringify({ error: 'Each target needs an API key' }),
+      { status: 400 },
+    );
+  }
+  const keyless = targets.find(
+    (t) => !t.api_key && t.provider !== keyProvider,
+  );
+  if (keyless) {
+    return new Response(
+      JSON.stringify({
+        error: `${keyless.provider} needs its own api_key; the request's API key is for ${keyProvider}`,
+      }),
+      { status: 400 },
+    );
+  }
+
+  let column: any;
+  let process: any;
+  try {
+    column = await getColumn(supabase, column_id);
+    process = await getProcess(supabase, column_id);
+  } catch (err) {
+    return new Response(
+      JSON.stringify({ error: err instanceof Error ? err.message : String(err) }),
+      { status: 500 },
+    );
+  }
+  if (!process) {
+    return new Response(
+      JSON.stringify({ error: 'Column has no process configured' }),
+      { status: 400 },
+    );
+  }
+
+  return createProgressStream(async ({ send, signal }) => {
+    try {
+      // Name copies by provider, adding the model when a provider repeats
+      const names = targets.map((t) =>
+        targets.filter((o) => o.provider === t.provider).length > 1
+          ? `${column.name} (${t.provider}: ${t.model})`
+          : `${column.name} (${t.provider})`,
+      );
+
+      const columnIds: string[] = [];
+      for (let i = 0; i < targets.length; i++) {
+        const newColumnId = await duplicateColumn(
+          supabase,
+          column_id,
+          false,
+          names[i],
+        );
+        await upsertProcess(supabase, {
+          column_id: newColumnId,
+          prompt: process.prompt,
+          model: targets[i].model,
+          task: process.task,
+          provider: targets[i].provider,
+          search_enabled: process.search_enabled,
+          image_column_id: process.image_column_id,
+        });
+        columnIds.push(newColumnId);
+        send('compare.column', {
+          provider: targets[i].provider,
+          model: targets[i].model,
+          column_id: newColumnId,
+          column_name: names[i],
+        });
+      }
+
+      const generateUrl = new URL('/api/generate', request.url);
+      let failedTargets = 0;
+
+      await Promise.all(
+        targets.map(async (target, i) => {
+          const progress: GenerationProgress = {
+            total: limit,
+            completed: 0,
+            failed: 0,
+          };
+          const reportError = (error: string) => {
+            failedTargets++;
+            send('compare.error', {
+              provider: target.provider,
+              model: target.model,
+              column_id: columnIds[i],
+              error,
+            });
+          };
+
+          let res: Response;
+          try {
+            res = await fetch(generateUrl.toString(), {
+              method: 'POST',
+              headers: {
+                'Content-Type': 'application/json',
+                'x-api-key': target.api_key || apiKey!,
+                'x-ai-provider': target.provider,
+                ...(rawExtraHeaders && { 'x-ai-extra-headers': rawExtraHeaders }),
+                Cookie: request.headers.get('cookie') || '',
+              },
+              body: JSON.stringify({
+                dataset_id,
+                column_id: columnIds[i],
+                offset,
+                limit,
+              }),
+              signal,
+            });
+          } catch (err) {
+            if (!signal.aborted) {
+              reportError(err instanceof Error ? err.message : String(err));
+            }
+            return;
+          }
+
+          if (!res.ok || !res.body) {
+            const { error } = await res.json().catch(() => ({ error: null }));
+            reportError(error || 'Generation failed');
+            return;
+          }
+
+          // The inner run outlives a dropped connection, so cancelling the
+          // comparison stops it directly as well
+          const innerOperationId = res.headers.get('X-Operation-Id');
+          const stopInner = () => {
+            if (innerOperationId) abortOperation(innerOperationId, user.id);
+          };
+          signal.addEventListener('abort', stopInner, { once: true });
+
+          try {
+            await readProgressEvents(res.body, (event) => {
+              if (event.event === 'generation.error') {
+                reportError(event.error);
+                return;
+              }
+              if (event.event !== 'cell.complete') return;
+              if (event.error) {
+                progress.failed++;
+              } else {
+                progress.completed++;
+              }
+              send('compare.progress', {
+                provider: target.provider,
+                model: target.model,
+                column_id: columnIds[i],
+                progress,
+              });
+            });
+          } catch (err) {
+            if (!signal.aborted) {
+              reportError(err instanceof Error ? err.message : String(err));
+            }
+          } finally {
+            signal.removeEventListener('abort', stopInner);
+          }
+        }),
+      );
+
+      if (signal.aborted) {
+        send('compare.aborted', { column_ids: columnIds });
+      } else if (failedTargets > 0) {
+        send('generic.error', {
+          error: `${failedTargets} of ${targets.length} comparisons failed`,
+          column_ids: columnIds,
+        });
+      } else {
+        send('compare.complete', { column_ids: columnIds });
+      }
+    } catch (err) {
+      send('generic.error', {
+        error: err instanceof Error ? err.message : String(err),
+      });
+    }
+  }, user.id);
+}
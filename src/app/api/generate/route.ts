@@ -1,7 +1,29 @@
 import { NextRequest } from 'next/server';
 import { createClient } from '@/lib/supabase/server';
-import { createOpenAIClient } from '@/lib/openai/client';
-import { generateText } from '@/lib/openai/text-generation';
+import { createProgressStream } from '@/lib/utils/progress-stream';
+import {
+  createOpenAIClient,
+  credentialScope,
+  parseExtraHeaders,
+} from '@/lib/openai/client';
+import {
+  generateChat,
+  generateText,
+  type ChatOptions,
+  type ChatResult,
+  type ReasoningEffort,
+} from '@/lib/openai/text-generation';
+import {
+  CIRCUIT_OPEN_ERROR,
+  canAttempt,
+  isProviderFailure,
+  recordFailure,
+  recordSuccess,
+  releaseAttempt,
+} from '@/lib/openai/circuit-breaker';
+import { QUOTA_EXCEEDED_ERROR } from '@/lib/openai/errors';
+import { withProviderSlot } from '@/lib/openai/provider-limiter';
+import { createWeightedPicker } from '@/lib/openai/weighted-picker';
 import { generateImage } from '@/lib/openai/image-generation';
 import { analyzeImage } from '@/lib/openai/vision';
 import { generateSpeech } from '@/lib/openai/speech';
@@ -9,30 +31,121 @@ import { transcribeAudio } from '@/lib/openai/transcription';
 import { searchWeb } from '@/lib/openai/web-search';
 import {
   materializePrompt,
+  parseMessagesPrompt,
   renderInstruction,
+  renderMessages,
   type Example,
 } from '@/lib/utils/prompt-template';
-import { upsertCellValue, getRowCells } from '@/lib/supabase/queries/cells';
-import { upsertCellMeta } from '@/lib/supabase/queries/cell-meta';
-import type { TaskType, Provider } from '@/lib/types/domain';
-import { MAX_CONCURRENCY, MERCURY_BASE_URL } from '@/lib/types/domain';
+import {
+  CHARS_PER_TOKEN,
+  estimateTokens,
+  exceedsContextWindow,
+  getContextWindow,
+  truncateDataToFit,
+} from '@/lib/utils/token-estimate';
+import {
+  getCellValues,
+  upsertCellValue,
+  getRowCells,
+  getRowData,
+} from '@/lib/supabase/queries/cells';
+import { getDatasetColumns } from '@/lib/supabase/queries/columns';
+import { hashCellInputs } from '@/lib/utils/cell-hash';
+import {
+  getCellMetaBatch,
+  upsertCellMeta,
+} from '@/lib/supabase/queries/cell-meta';
+import {
+  deleteActiveRun,
+  insertGenerationRun,
+  startActiveRun,
+  toResumableRequest,
+  updateActiveRunProgress,
+} from '@/lib/supabase/queries/generation-runs';
+import { getProcess } from '@/lib/supabase/queries/processes';
+import { getDataset } from '@/lib/supabase/queries/datasets';
+import { getUserSettings } from '@/lib/supabase/queries/user-settings';
+import type {
+  ChatMessage,
+  GenerationProgress,
+  TaskType,
+  Provider,
+} from '@/lib/types/domain';
+import {
+  MAX_CELL_BYTES,
+  MAX_CONCURRENCY,
+  MERCURY_BASE_URL,
+} from '@/lib/types/domain';
+import { isOversized, truncateToBytes } from '@/lib/utils/cell-size';
+import {
+  findMatchingRows,
+  ROW_FILTER_OPS,
+  type RowFilter,
+} from '@/lib/utils/row-filter';
 
 export const maxDuration = 300;
 
+const PROVIDERS: Provider[] = ['openai', 'mercury'];
+// PostgREST caps responses at 1000 rows
+const STALE_PAGE_SIZE = 1000;
+
+interface GenerateProcess {
+  prompt: string;
+  model: string;
+  task: TaskType;
+  search_enabled: boolean;
+  image_column_id?: string;
+  columns_references?: string[];
+  messages?: ChatMessage[];
+}
+
+interface WeightedProvider {
+  provider: Provider;
+  weight: number;
+  // Defaults to the process model
+  model?: string;
+  // Defaults to the request's x-api-key
+  api_key?: string;
+}
+
 interface GenerateRequest {
   dataset_id: string;
   column_id: string;
   column_name: string;
-  process: {
-    prompt: string;
-    model: string;
-    task: TaskType;
-    search_enabled: boolean;
-    image_column_id?: string;
-    columns_references?: string[];
-  };
+  // Falls back to the column's saved process when omitted
+  process?: GenerateProcess;
   offset: number;
   limit: number;
+  on_prompt_overflow?: 'error' | 'truncate';
+  max_cell_bytes?: number;
+  on_oversized_cell?: 'truncate' | 'reject';
+  logprobs?: boolean;
+  top_logprobs?: number;
+  reasoning_effort?: ReasoningEffort;
+  force_json?: boolean;
+  greedy?: boolean;
+  // Stop cleanly after this row; resume by posting the returned offset/limit
+  breakpoint_row?: number;
+  // Abandon a cell that takes longer than this; unset means no limit
+  cell_timeout_secs?: number;
+  tools?: ChatOptions['tools'];
+  // Only regenerate cells whose inputs changed since they were generated
+  only_stale?: boolean;
+  // Spread cells over several providers in proportion to their weights
+  providers?: WeightedProvider[];
+  // Only generate rows where every filter matches
+  filters?: RowFilter[];
+}
+
+interface CellResult {
+  value?: any;
+  error?: string;
+  error_kind?: ChatResult['error_kind'];
+  error_status?: ChatResult['error_status'];
+  sources?: any[];
+  prompt?: string;
+  logprobs?: ChatResult['logprobs'];
+  tool_calls?: ChatResult['tool_calls'];
 }
 
 export async function POST(request: NextRequest) {
@@ -43,18 +156,84 @@ export async function POST(request: NextRequest) {
       { status: 401 },
     );
   }
-  const provider = (request.headers.get('x-ai-provider') || 'openai') as Provider;
-  const baseURL = provider === 'mercury' ? MERCURY_BASE_URL : undefined;
+
+  let extraHeaders: Record<string, string> | undefined;
+  try {
+    extraHeaders = parseExtraHeaders(request.headers.get('x-ai-extra-headers'));
+  } catch (err) {
+    return new Response(
+      JSON.stringify({ error: err instanceof Error ? err.message : String(err) }),
+      { status: 400 },
+    );
+  }
 
   const body: GenerateRequest = await request.json();
   const {
     dataset_id,
     column_id,
-    process: proc,
     offset,
     limit,
+    on_prompt_overflow = 'error',
+    max_cell_bytes = MAX_CELL_BYTES,
+    on_oversized_cell = 'truncate',
+    logprobs = false,
+    top_logprobs,
+    reasoning_effort,
+    force_json = false,
+    greedy = false,
+    breakpoint_row,
+    cell_timeout_secs,
+    tools,
+    only_stale = false,
+    providers,
+    filters,
   } = body;
 
+  if (
+    filters != null &&
+    (!Array.isArray(filters) ||
+      filters.some((f) => !f?.column_id || !ROW_FILTER_OPS.includes(f.op)))
+  ) {
+    return new Response(
+      JSON.stringify({
+        error: `Each filter needs a column_id and an op: ${ROW_FILTER_OPS.join(', ')}`,
+      }),
+      { status: 400 },
+    );
+  }
+
+  if (
+    providers != null &&
+    (!Array.isArray(providers) ||
+      providers.length === 0 ||
+      providers.some((p) => !PROVIDERS.includes(p.provider) || !(p.weight > 0)))
+  ) {
+    return new Response(
+      JSON.stringify({
+        error: `Each provider needs a name (${PROVIDERS.join(', ')}) and a positive weight`,
+      }),
+      { status: 400 },
+    );
+  }
+
+  const headerProvider = request.headers.get('x-ai-provider');
+  if (headerProvider && !PROVIDERS.includes(headerProvider as Provider)) {
+    return new Response(
+      JSON.stringify({
+        error: `x-ai-provider must be one of: ${PROVIDERS.join(', ')}`,
+      }),
+      { status: 400 },
+    );
+  }
+  const chatOptions: ChatOptions = {
+    logprobs,
+    top_logprobs,
+    reasoning_effort,
+    force_json,
+    greedy,
+    tools,
+  };
+
   const supabase = await createClient();
 
   const {
@@ -67,48 +246,256 @@ export async function POST(request: NextRequest) {
     );
   }
 
-  const openai = createOpenAIClient(apiKey, baseURL);
+  const storedProcess = await getProcess(supabase, column_id);
+  const proc: GenerateProcess | undefined =
+    body.process ??
+    (storedProcess
+      ? {
+          prompt: storedProcess.prompt,
+          model: storedProcess.model,
+          task: storedProcess.task,
+          search_enabled: storedProcess.search_enabled,
+          image_column_id: storedProcess.image_column_id ?? undefined,
+          columns_references: storedProcess.process_columns.map(
+            (pc: { column_id: string }) => pc.column_id,
+          ),
+        }
+      : undefined);
+  if (!proc) {
+    return new Response(
+      JSON.stringify({ error: 'Column has no process configured' }),
+      { status: 400 },
+    );
+  }
 
-  const encoder = new TextEncoder();
-  const stream = new ReadableStream({
-    async start(controller) {
-      const send = (event: string, data: any) => {
-        controller.enqueue(
-          encoder.encode(`data: ${JSON.stringify({ event, ...data })}\n\n`),
-        );
-      };
+  const settings = await getUserSettings(supabase, user.id);
+  const storePrompts = settings?.store_prompts ?? false;
+
+  // The API key is for the header's provider, so a pinned or default
+  // provider is only used when it matches; anything else is rejected rather
+  // than sending the key to the wrong vendor. Without a header the key is
+  // taken to be for the configured provider.
+  const pinnedProvider: Provider | null = storedProcess?.provider ?? null;
+  const configuredProvider: Provider | null =
+    pinnedProvider ||
+    (await getDataset(supabase, dataset_id)).default_provider ||
+    settings?.default_provider ||
+    null;
+  if (
+    !providers &&
+    headerProvider &&
+    configuredProvider &&
+    configuredProvider !== headerProvider
+  ) {
+    return new Response(
+      JSON.stringify({
+        error: pinnedProvider
+          ? `This column is pinned to ${configuredProvider}, but the API key is for ${headerProvider}`
+          : `The default provider is ${configuredProvider}, but the API key is for ${headerProvider}`,
+      }),
+      { status: 400 },
+    );
+  }
+
+  const provider: Provider =
+    (headerProvider as Provider | null) || configuredProvider || 'openai';
+
+  // The request's key only goes to its own provider; others bring their own
+  const keyless = providers?.find((p) => !p.api_key && p.provider !== provider);
+  if (keyless) {
+    return new Response(
+      JSON.stringify({
+        error: `${keyless.provider} needs its own api_key; the request's API key is for ${provider}`,
+      }),
+      { status: 400 },
+    );
+  }
+  const baseURL = provider === 'mercury' ? MERCURY_BASE_URL : undefined;
+
+  const openai = createOpenAIClient(apiKey, baseURL, extraHeaders);
+
+  const targets = providers
+    ? providers.map((p) => ({
+        provider: p.provider,
+        model: p.model || proc.model,
+        weight: p.weight,
+        scope: credentialScope(p.api_key || apiKey),
+        client: createOpenAIClient(
+          p.api_key || apiKey,
+          p.provider === 'mercury' ? MERCURY_BASE_URL : undefined,
+          extraHeaders,
+        ),
+        completed: 0,
+        failed: 0,
+      }))
+    : [
+        {
+          provider,
+          model: proc.model,
+          weight: 1,
+          scope: credentialScope(apiKey),
+          client: openai,
+          completed: 0,
+          failed: 0,
+        },
+      ];
+  const picker = createWeightedPicker(targets);
 
+  return createProgressStream(async ({ operationId, send, signal }) => {
+    const startedAt = new Date().toISOString();
+    const end =
+      breakpoint_row != null
+        ? Math.min(offset + limit, Math.max(breakpoint_row + 1, offset))
+        : offset + limit;
+    const progress: GenerationProgress = {
+      total: end - offset,
+      completed: 0,
+      failed: 0,
+    };
+
+    const recordRun = async (error?: string) => {
       try {
-        const existingExamples: Example[] = [];
-
-        // Process cells in batches of MAX_CONCURRENCY
-        for (let i = offset; i < offset + limit; i += MAX_CONCURRENCY) {
-          const batchEnd = Math.min(i + MAX_CONCURRENCY, offset + limit);
-          const batchPromises: Promise<void>[] = [];
-
-          for (let rowIdx = i; rowIdx < batchEnd; rowIdx++) {
-            batchPromises.push(
-              (async () => {
-                // Mark cell as generating
-                send('cell.generating', { row_idx: rowIdx, column_id });
-                await upsertCellMeta(supabase, {
-                  column_id,
-                  row_idx: rowIdx,
-                  generating: true,
-                });
+        await insertGenerationRun(supabase, {
+          dataset_id,
+          column_id,
+          provider: [...new Set(targets.map((t) => t.provider))].join(','),
+          model: [...new Set(targets.map((t) => t.model))].join(','),
+          task: proc.task,
+          ...progress,
+          error: error || null,
+          started_at: startedAt,
+        });
+      } catch {
+        // The run history is best-effort; never fail generation over it
+      }
+    };
+
+    // Tracked while running so an interrupted run can be resumed; the row is
+    // only left behind if the server dies mid-run
+    let tracked = false;
+    try {
+      await startActiveRun(supabase, {
+        id: operationId,
+        dataset_id,
+        column_id,
+        request: { ...toResumableRequest(body), offset, limit },
+        end_row: end,
+      });
+      tracked = true;
+    } catch {
+      // Resuming is best-effort; never fail generation over it
+    }
+
+    try {
+      const existingExamples: Example[] = [];
+
+      let rowIndices = Array.from({ length: end - offset }, (_, i) => offset + i);
+      if (only_stale) {
+        rowIndices = await findStaleRows(
+          supabase,
+          proc,
+          dataset_id,
+          column_id,
+          offset,
+          end,
+        );
+        progress.total = rowIndices.length;
+        send('generation.stale', {
+          column_id,
+          checked: end - offset,
+          stale: rowIndices.length,
+        });
+      }
+      if (filters && filters.length > 0) {
+        const matching = new Set(
+          await findMatchingRows(supabase, dataset_id, filters, offset, end),
+        );
+        rowIndices = rowIndices.filter((rowIdx) => matching.has(rowIdx));
+        progress.total = rowIndices.length;
+        send('generation.filtered', {
+          column_id,
+          checked: end - offset,
+          matched: rowIndices.length,
+        });
+      }
 
-                let result: { value?: any; error?: string; sources?: any[] };
+      let quotaExceeded = false;
 
+      // Process cells in batches of MAX_CONCURRENCY
+      for (
+        let i = 0;
+        i < rowIndices.length && !signal.aborted && !quotaExceeded;
+        i += MAX_CONCURRENCY
+      ) {
+        const batchPromises: Promise<void>[] = [];
+
+        for (const rowIdx of rowIndices.slice(i, i + MAX_CONCURRENCY)) {
+          batchPromises.push(
+            (async () => {
+              // Mark cell as generating
+              send('cell.generating', { row_idx: rowIdx, column_id });
+              await upsertCellMeta(supabase, {
+                column_id,
+                row_idx: rowIdx,
+                generating: true,
+              });
+
+              let result: CellResult;
+              let promptHash: string | undefined;
+              const target = picker.pick();
+
+              if (!target) {
+                // Every provider ran out of quota during this batch
+                result = { error: QUOTA_EXCEEDED_ERROR, error_kind: 'quota' };
+              } else if (!canAttempt(target.scope, target.provider)) {
+                // Fail fast while the provider's circuit is open
+                result = { error: `${CIRCUIT_OPEN_ERROR}: ${target.provider}` };
+              } else {
+                const cellProc = { ...proc, model: target.model };
                 try {
-                  result = await generateSingleCell({
-                    openai,
+                  const data = await getRowData(
                     supabase,
-                    proc,
-                    dataset_id,
-                    column_id,
                     rowIdx,
-                    existingExamples,
-                  });
+                    proc.columns_references ?? [],
+                  );
+                  // Hashed with the process model, as findStaleRows does, so
+                  // a cell doesn't look stale for the target it landed on
+                  promptHash = hashCellInputs(proc, data);
+
+                  // Shared provider slot, so concurrent runs together stay
+                  // under the provider's limit; the timeout starts once a
+                  // slot is held. A timed-out cell aborts its own request so
+                  // the slot isn't freed while the request still runs.
+                  const cellController = new AbortController();
+                  const abortCell = () => cellController.abort();
+                  signal.addEventListener('abort', abortCell);
+                  result = await withProviderSlot(
+                    target.scope,
+                    target.provider,
+                    () =>
+                      withTimeout(
+                        generateSingleCell({
+                          openai: target.client,
+                          supabase,
+                          proc: cellProc,
+                          dataset_id,
+                          column_id,
+                          rowIdx,
+                          data,
+                          existingExamples,
+                          onPromptOverflow: on_prompt_overflow,
+                          // Stable per cell within this run, fresh on regeneration
+                          chatOptions: {
+                            ...chatOptions,
+                            idempotency_key: `${operationId}:${column_id}:${rowIdx}`,
+                            signal: cellController.signal,
+                          },
+                        }),
+                        cell_timeout_secs,
+                        cellController,
+                      ),
+                    cellController.signal,
+                  ).finally(() => signal.removeEventListener('abort', abortCell));
                 } catch (err) {
                   result = {
                     error:
@@ -116,66 +503,244 @@ export async function POST(request: NextRequest) {
                   };
                 }
 
-                // Save the result
-                if (result.value !== undefined) {
-                  await upsertCellValue(supabase, {
-                    dataset_id,
-                    column_id,
-                    row_idx: rowIdx,
-                    value: result.value,
-                  });
+                if (isProviderFailure(result)) {
+                  recordFailure(target.scope, target.provider);
+                } else if (!result.error || result.error_status !== undefined) {
+                  // Any answer from the provider shows it's reachable
+                  recordSuccess(target.scope, target.provider);
+                } else {
+                  releaseAttempt(target.scope, target.provider);
+                }
+                if (result.error) {
+                  target.failed++;
+                } else {
+                  target.completed++;
+                }
+                // A provider out of quota gets no more cells; the run stops
+                // once none are left
+                if (result.error_kind === 'quota') {
+                  picker.disable(target);
+                  quotaExceeded = picker.allDisabled();
+                }
+              }
+
+              // Keep huge text outputs from bloating every table load
+              let warning: string | undefined;
+              if (
+                typeof result.value === 'string' &&
+                (proc.task === 'text-generation' ||
+                  proc.task === 'image-text-to-text') &&
+                isOversized(result.value, max_cell_bytes)
+              ) {
+                if (on_oversized_cell === 'reject') {
+                  result = {
+                    error: `Output exceeds the ${max_cell_bytes}-byte cell limit`,
+                  };
+                } else {
+                  result.value = truncateToBytes(result.value, max_cell_bytes);
+                  warning = `Output truncated to ${max_cell_bytes} bytes`;
                 }
+              }
 
-                await upsertCellMeta(supabase, {
+              // Save the result
+              if (result.value !== undefined) {
+                await upsertCellValue(supabase, {
+                  dataset_id,
                   column_id,
                   row_idx: rowIdx,
-                  generating: false,
-                  error: result.error || null,
-                  sources: result.sources || null,
+                  value: result.value,
                 });
+              }
 
-                // Add to examples for deduplication
-                if (result.value && !result.error) {
-                  existingExamples.push({
-                    output: String(result.value),
-                    inputs: {},
-                    validated: false,
-                  });
-                }
+              await upsertCellMeta(supabase, {
+                column_id,
+                row_idx: rowIdx,
+                generating: false,
+                error: result.error || null,
+                error_status: result.error ? result.error_status ?? null : null,
+                sources: result.sources || null,
+                prompt_used: storePrompts ? result.prompt ?? null : null,
+                // Failed cells keep no hash so they always count as stale
+                prompt_hash: result.error ? null : promptHash ?? null,
+              });
 
-                send('cell.complete', {
-                  row_idx: rowIdx,
-                  column_id,
-                  value: result.value,
-                  error: result.error,
-                  sources: result.sources,
+              if (result.error) {
+                progress.failed++;
+              } else {
+                progress.completed++;
+              }
+
+              // Add to examples for deduplication
+              if (result.value && !result.error) {
+                existingExamples.push({
+                  output: String(result.value),
+                  inputs: {},
+                  validated: false,
                 });
-              })(),
-            );
-          }
+              }
 
-          // Wait for batch to complete
-          await Promise.all(batchPromises);
+              send('cell.complete', {
+                row_idx: rowIdx,
+                column_id,
+                value: result.value,
+                error: result.error,
+                error_kind: result.error_kind,
+                error_status: result.error_status,
+                warning,
+                sources: result.sources,
+                logprobs: result.logprobs,
+                tool_calls: result.tool_calls,
+              });
+            })(),
+          );
         }
 
-        send('generation.complete', { column_id });
-        controller.close();
-      } catch (err) {
+        // Wait for batch to complete
+        await Promise.all(batchPromises);
+
+        if (targets.length > 1) {
+          progress.providers = Object.fromEntries(
+            targets.map((t) => [
+              `${t.provider}:${t.model}`,
+              { completed: t.completed, failed: t.failed },
+            ]),
+          );
+        }
+
+        if (tracked) {
+          const lastRow =
+            rowIndices[Math.min(i + MAX_CONCURRENCY, rowIndices.length) - 1];
+          await updateActiveRunProgress(supabase, operationId, lastRow).catch(
+            () => {},
+          );
+        }
+      }
+
+      if (quotaExceeded) {
+        await recordRun(QUOTA_EXCEEDED_ERROR);
         send('generation.error', {
-          error: err instanceof Error ? err.message : String(err),
+          column_id,
+          error: `${QUOTA_EXCEEDED_ERROR}: ${targets
+            .map((t) => t.provider)
+            .join(', ')}`,
+          error_kind: 'quota',
+          progress,
         });
-        controller.close();
+        return;
+      }
+
+      if (signal.aborted) {
+        await recordRun('Aborted');
+        send('generation.aborted', { column_id, progress });
+        return;
       }
-    },
-  });
 
-  return new Response(stream, {
-    headers: {
-      'Content-Type': 'text/event-stream',
-      'Cache-Control': 'no-cache',
-      Connection: 'keep-alive',
-    },
+      await recordRun();
+      if (end < offset + limit) {
+        send('generation.paused', {
+          column_id,
+          progress,
+          resume: { offset: end, limit: offset + limit - end },
+        });
+      } else {
+        send('generation.complete', { column_id, progress });
+      }
+    } catch (err) {
+      const message = err instanceof Error ? err.message : String(err);
+      await recordRun(message);
+      send('generation.error', { error: message });
+    } finally {
+      if (tracked) await deleteActiveRun(supabase, operationId).catch(() => {});
+    }
+  }, user.id);
+}
+
+/**
+ * Rows in [offset, end) whose stored input hash is missing or no longer
+ * matches the current prompt, model and referenced values.
+ */
+async function findStaleRows(
+  supabase: any,
+  proc: GenerateProcess,
+  dataset_id: string,
+  column_id: string,
+  offset: number,
+  end: number,
+): Promise<number[]> {
+  const metas = await getCellMetaBatch(supabase, column_id, offset, end - offset);
+  const storedHashes = new Map<number, string | null>(
+    metas.map((m: any) => [m.row_idx, m.prompt_hash]),
+  );
+
+  // Load the referenced values for the whole range up front, keyed by column
+  // name like getRowData, instead of querying row by row
+  const referenceIds = proc.columns_references ?? [];
+  const columnNames = new Map<string, string>(
+    referenceIds.length > 0
+      ? (await getDatasetColumns(supabase, dataset_id)).map((c: any) => [
+          c.id,
+          c.name,
+        ])
+      : [],
+  );
+  const rowData = new Map<number, Record<string, any>>();
+  for (const referenceId of referenceIds) {
+    for (let from = offset; from < end; from += STALE_PAGE_SIZE) {
+      const cells = await getCellValues(
+        supabase,
+        dataset_id,
+        [referenceId],
+        from,
+        Math.min(STALE_PAGE_SIZE, end - from),
+      );
+      for (const cell of cells) {
+        const data = rowData.get(cell.row_idx) ?? {};
+        data[columnNames.get(cell.column_id) || cell.column_id] = cell.value;
+        rowData.set(cell.row_idx, data);
+      }
+    }
+  }
+
+  const stale: number[] = [];
+  for (let rowIdx = offset; rowIdx < end; rowIdx++) {
+    const storedHash = storedHashes.get(rowIdx);
+    if (
+      !storedHash ||
+      hashCellInputs(proc, rowData.get(rowIdx) ?? {}) !== storedHash
+    ) {
+      stale.push(rowIdx);
+    }
+  }
+  return stale;
+}
+
+/**
+ * Abandons a cell that hasn't finished within the timeout so one hung request
+ * can't stall the whole run, aborting the cell's in-flight request.
+ */
+async function withTimeout(
+  cell: Promise<CellResult>,
+  timeoutSecs: number | undefined,
+  controller: AbortController,
+): Promise<CellResult> {
+  if (!timeoutSecs) return cell;
+
+  let timer: ReturnType<typeof setTimeout> | undefined;
+  const timeout = new Promise<CellResult>((resolve) => {
+    timer = setTimeout(() => {
+      controller.abort();
+      resolve({
+        error: `Cell generation timed out after ${timeoutSecs}s`,
+        error_kind: 'timeout',
+      });
+    }, timeoutSecs * 1000);
   });
+
+  try {
+    return await Promise.race([cell, timeout]);
+  } finally {
+    clearTimeout(timer);
+  }
 }
 
 async function generateSingleCell({
@@ -185,40 +750,36 @@ async function generateSingleCell({
   dataset_id,
   column_id,
   rowIdx,
+  data,
   existingExamples,
+  onPromptOverflow,
+  chatOptions,
 }: {
   openai: any;
   supabase: any;
-  proc: GenerateRequest['process'];
+  proc: GenerateProcess;
   dataset_id: string;
   column_id: string;
   rowIdx: number;
+  data: Record<string, any>;
   existingExamples: Example[];
-}): Promise<{ value?: any; error?: string; sources?: any[] }> {
-  // Build data context from referenced columns
-  let data: Record<string, any> = {};
+  onPromptOverflow: 'error' | 'truncate';
+  chatOptions: ChatOptions;
+}): Promise<CellResult> {
   const hasRefs = proc.columns_references && proc.columns_references.length > 0;
 
-  if (hasRefs) {
-    const rowCells = await getRowCells(
-      supabase,
-      rowIdx,
-      proc.columns_references!,
-    );
-
-    if (rowCells && rowCells.length > 0) {
-      data = Object.fromEntries(
-        rowCells.map((cell: any) => [cell.columns?.name || cell.column_id, cell.value]),
-      );
-    }
-  }
-
   switch (proc.task) {
     case 'text-to-image': {
       const finalPrompt = hasRefs
         ? renderInstruction(proc.prompt, data)
         : proc.prompt;
-      return generateImage(openai, finalPrompt, proc.model);
+      const result = await generateImage(
+        openai,
+        finalPrompt,
+        proc.model,
+        chatOptions.signal,
+      );
+      return { ...result, prompt: finalPrompt };
     }
 
     case 'image-text-to-text': {
@@ -236,20 +797,36 @@ async function generateSingleCell({
       const finalPrompt = hasRefs
         ? renderInstruction(proc.prompt, data)
         : proc.prompt;
-      return analyzeImage(openai, String(imageUrl), finalPrompt, proc.model);
+      const result = await analyzeImage(
+        openai,
+        String(imageUrl),
+        finalPrompt,
+        proc.model,
+        chatOptions.signal,
+      );
+      return { ...result, prompt: finalPrompt };
     }
 
     case 'speech': {
       const textToSpeak = hasRefs
         ? renderInstruction(proc.prompt, data)
         : proc.prompt;
-      const speechResult = await generateSpeech(openai, textToSpeak);
+      const speechResult = await generateSpeech(
+        openai,
+        textToSpeak,
+        'alloy',
+        'tts-1',
+        chatOptions.signal,
+      );
       if (speechResult.value) {
         // Convert to base64 data URI for storage
         const base64 = Buffer.from(speechResult.value).toString('base64');
-        return { value: `data:audio/mp3;base64,${base64}` };
+        return {
+          value: `data:audio/mp3;base64,${base64}`,
+          prompt: textToSpeak,
+        };
       }
-      return { error: speechResult.error };
+      return { error: speechResult.error, prompt: textToSpeak };
     }
 
     case 'transcription': {
@@ -270,10 +847,55 @@ async function generateSingleCell({
         '',
       );
       const buffer = Buffer.from(base64Data, 'base64');
-      return transcribeAudio(openai, buffer.buffer as ArrayBuffer);
+      return transcribeAudio(
+        openai,
+        buffer.buffer as ArrayBuffer,
+        'whisper-1',
+        chatOptions.signal,
+      );
     }
 
     default: {
+      // Multi-turn prompts are forwarded as-is, with references rendered per message
+      const messages = proc.messages ?? parseMessagesPrompt(proc.prompt);
+      if (messages) {
+        let rendered = renderMessages(messages, data);
+
+        // Same context window guard as single prompts, over all messages
+        const messagesText = (msgs: ChatMessage[]) =>
+          msgs.map((m) => m.content).join('\n');
+        if (exceedsContextWindow(messagesText(rendered), proc.model)) {
+          const contextWindow = getContextWindow(proc.model);
+          const promptTokens = estimateTokens(messagesText(rendered));
+          if (onPromptOverflow !== 'truncate' || !hasRefs) {
+            return {
+              error: `Prompt too long: ~${promptTokens} tokens exceeds the ${contextWindow}-token context window of ${proc.model}`,
+              error_kind: 'context_length',
+            };
+          }
+          const overflowChars =
+            messagesText(rendered).length - contextWindow * CHARS_PER_TOKEN;
+          rendered = renderMessages(
+            messages,
+            truncateDataToFit(data, overflowChars),
+          );
+          if (exceedsContextWindow(messagesText(rendered), proc.model)) {
+            return {
+              error: `Prompt too long: cannot fit ${proc.model}'s ${contextWindow}-token context window even after truncating referenced values`,
+              error_kind: 'context_length',
+            };
+          }
+        }
+
+        const result = await generateChat(
+          openai,
+          rendered,
+          proc.model,
+          chatOptions,
+        );
+        return { ...result, prompt: JSON.stringify(rendered, null, 2) };
+      }
+
       // text-generation
       let sourcesContext: { source_uri: string; text: string }[] | undefined;
       let sources: { url: string; snippet: string }[] | undefined;
@@ -282,7 +904,12 @@ async function generateSingleCell({
         const searchQuery = hasRefs
           ? renderInstruction(proc.prompt, data)
           : proc.prompt;
-        const searchResult = await searchWeb(openai, searchQuery, proc.model);
+        const searchResult = await searchWeb(
+          openai,
+          searchQuery,
+          proc.model,
+          chatOptions.signal,
+        );
         if (searchResult.sources) {
           sourcesContext = searchResult.sources.map((s) => ({
             source_uri: s.url,
@@ -292,7 +919,7 @@ async function generateSingleCell({
         }
       }
 
-      const prompt = materializePrompt({
+      let prompt = materializePrompt({
         instruction: proc.prompt,
         data: hasRefs ? data : undefined,
         examples: existingExamples,
@@ -300,8 +927,34 @@ async function generateSingleCell({
         task: proc.task,
       });
 
-      const result = await generateText(openai, prompt, proc.model);
-      return { ...result, sources };
+      // Guard against prompts that would overflow the model's context window
+      if (exceedsContextWindow(prompt, proc.model)) {
+        const contextWindow = getContextWindow(proc.model);
+        const promptTokens = estimateTokens(prompt);
+        if (onPromptOverflow !== 'truncate' || !hasRefs) {
+          return {
+            error: `Prompt too long: ~${promptTokens} tokens exceeds the ${contextWindow}-token context window of ${proc.model}`,
+            error_kind: 'context_length',
+          };
+        }
+        const overflowChars = prompt.length - contextWindow * CHARS_PER_TOKEN;
+        prompt = materializePrompt({
+          instruction: proc.prompt,
+          data: truncateDataToFit(data, overflowChars),
+          examples: existingExamples,
+          sourcesContext,
+          task: proc.task,
+        });
+        if (exceedsContextWindow(prompt, proc.model)) {
+          return {
+            error: `Prompt too long: cannot fit ${proc.model}'s ${contextWindow}-token context window even after truncating referenced values`,
+            error_kind: 'context_length',
+          };
+        }
+      }
+
+      const result = await generateText(openai, prompt, proc.model, chatOptions);
+      return { ...result, sources, prompt };
     }
   }
 }
@@ -0,0 +1,28 @@
+import { NextRequest, NextResponse } from 'next/server';
+import { createClient } from '@/lib/supabase/server';
+import { getGenerationRuns } from '@/lib/supabase/queries/generation-runs';
+
+export async function GET(request: NextRequest) {
+  const supabase = await createClient();
+
+  const {
+    data: { user },
+  } = await supabase.auth.getUser();
+  if (!user) {
+    return NextResponse.json({ error: 'Unauthorized' }, { status: 401 });
+  }
+
+  const { searchParams } = new URL(request.url);
+  const datasetId = searchParams.get('dataset_id');
+  const limit = Number(searchParams.get('limit')) || 20;
+
+  if (!datasetId) {
+    return NextResponse.json(
+      { error: 'Missing dataset_id' },
+      { status: 400 },
+    );
+  }
+
+  const runs = await getGenerationRuns(supabase, datasetId, limit);
+  return NextResponse.json({ runs });
+}
@@ -0,0 +1,70 @@
+import { NextRequest, NextResponse } from 'next/server';
+import { createClient } from '@/lib/supabase/server';
+import {
+  createDataset,
+  deleteDataset,
+} from '@/lib/supabase/queries/datasets';
+import { importRows } from '@/lib/utils/import-rows';
+import Papa from 'papaparse';
+import { MAX_ROWS_IMPORT } from '@/lib/types/domain';
+
+/**
+ * Imports each uploaded CSV into its own dataset, named after the file. A
+ * file that fails is rolled back on its own without aborting the batch.
+ */
+export async function POST(request: NextRequest) {
+  const supabase = await createClient();
+  const {
+    data: { user },
+  } = await supabase.auth.getUser();
+
+  if (!user) {
+    return NextResponse.json({ error: 'Unauthorized' }, { status: 401 });
+  }
+
+  const formData = await request.formData();
+  const files = formData.getAll('files') as File[];
+
+  if (files.length === 0) {
+    return NextResponse.json({ error: 'Missing files' }, { status: 400 });
+  }
+
+  const created: Array<{ file: string; dataset_id: string; rows: number }> =
+    [];
+  const failed: Array<{ file: string; error: string }> = [];
+
+  for (const file of files) {
+    let datasetId: string | undefined;
+    try {
+      const parsed = Papa.parse(await file.text(), {
+        header: true,
+        skipEmptyLines: true,
+      });
+      const rows = (parsed.data as Record<string, any>[]).slice(
+        0,
+        MAX_ROWS_IMPORT,
+      );
+      if (rows.length === 0) {
+        throw new Error('No data found in file');
+      }
+
+      const name = file.name.replace(/\.csv$/i, '') || 'Untitled';
+      const dataset = await createDataset(supabase, name, user.id);
+      datasetId = dataset.id;
+
+      const result = await importRows(supabase, dataset.id, rows);
+      created.push({ file: file.name, dataset_id: dataset.id, rows: result.rows });
+    } catch (err) {
+      // Drop the partial dataset; columns and cells cascade with it
+      if (datasetId) {
+        await deleteDataset(supabase, datasetId).catch(() => {});
+      }
+      failed.push({
+        file: file.name,
+        error: err instanceof Error ? err.message : String(err),
+      });
+    }
+  }
+
+  return NextResponse.json({ created, failed });
+}
@@ -0,0 +1,161 @@
+import { NextRequest } from 'next/server';
+import { createClient } from '@/lib/supabase/server';
+import { createProgressStream } from '@/lib/utils/progress-stream';
+import { bulkUpsertCellValues } from '@/lib/supabase/queries/cells';
+import {
+  createImportColumns,
+  normalizeImportValue,
+  parseNormalization,
+  type ImportNormalization,
+} from '@/lib/utils/import-rows';
+
+export const maxDuration = 300;
+
+const BATCH_ROWS = 500;
+// Line numbers reported for malformed lines; the rest are only counted
+const MAX_REPORTED_ERRORS = 10;
+
+/**
+ * Imports a JSONL file sent as the raw request body, parsing each line on its
+ * own as it streams in. With `skip_errors=true` malformed lines are skipped
+ * and counted; otherwise the first one stops the import. Columns are the
+ * union of keys seen, created as new keys appear.
+ */
+export async function POST(request: NextRequest) {
+  const supabase = await createClient();
+  const {
+    data: { user },
+  } = await supabase.auth.getUser();
+
+  if (!user) {
+    return new Response(JSON.stringify({ error: 'Unauthorized' }), {
+      status: 401,
+    });
+  }
+
+  const { searchParams } = new URL(request.url);
+  const datasetId = searchParams.get('dataset_id');
+  const skipErrors = searchParams.get('skip_errors') === 'true';
+  if (!datasetId || !request.body) {
+    return new Response(
+      JSON.stringify({ error: 'Missing dataset_id or file body' }),
+      { status: 400 },
+    );
+  }
+
+  let normalization: ImportNormalization;
+  try {
+    normalization = parseNormalization(searchParams.get('normalize'));
+  } catch (err) {
+    return new Response(
+      JSON.stringify({ error: err instanceof Error ? err.message : String(err) }),
+      { status: 400 },
+    );
+  }
+  const body = request.body;
+
+  return createProgressStream(async ({ send, signal }) => {
+    const columnMap: Record<string, string> = {};
+    const errorLines: number[] = [];
+    let skipped = 0;
+    let rowIdx = 0;
+    let lineNo = 0;
+    let batch: Parameters<typeof bulkUpsertCellValues>[1] = [];
+
+    const flush = async () => {
+      if (batch.length === 0) return;
+      await bulkUpsertCellValues(supabase, batch);
+      batch = [];
+      send('import.progress', { rows: rowIdx, skipped });
+    };
+
+    // Returns false when a malformed line should stop the import
+    const handleLine = async (line: string) => {
+      lineNo++;
+      if (!line.trim()) return true;
+
+      let row: unknown;
+      try {
+        row = JSON.parse(line);
+      } catch {
+        row = undefined;
+      }
+      if (!row || typeof row !== 'object' || Array.isArray(row)) {
+        if (!skipErrors) {
+          send('import.error', { error: `Malformed JSON on line ${lineNo}` });
+          return false;
+        }
+        skipped++;
+        if (errorLines.length < MAX_REPORTED_ERRORS) errorLines.push(lineNo);
+        return true;
+      }
+
+      const record = row as Record<string, any>;
+      const newNames = Object.keys(record).filter((name) => !columnMap[name]);
+      if (newNames.length > 0) {
+        const created = await createImportColumns(supabase, datasetId, newNames);
+        Object.assign(columnMap, created.columnMap);
+      }
+
+      for (const [name, value] of Object.entries(record)) {
+        batch.push({
+          dataset_id: datasetId,
+          column_id: columnMap[name],
+          row_idx: rowIdx,
+          value: normalizeImportValue(value ?? null, normalization),
+        });
+      }
+      rowIdx++;
+
+      if (rowIdx % BATCH_ROWS === 0) {
+        await flush();
+      }
+      return true;
+    };
+
+    try {
+      const reader = body.getReader();
+      const decoder = new TextDecoder();
+      let buffer = '';
+      let stopped = false;
+
+      while (!stopped && !signal.aborted) {
+        const { done, value } = await reader.read();
+        if (done) break;
+
+        buffer += decoder.decode(value, { stream: true });
+        const lines = buffer.split('\n');
+        buffer = lines.pop() || '';
+        for (const line of lines) {
+          if (!(await handleLine(line))) {
+            stopped = true;
+            break;
+          }
+        }
+      }
+      if (!stopped && !signal.aborted && buffer) {
+        stopped = !(await handleLine(buffer));
+      }
+      await flush();
+      if (stopped) return;
+
+      const summary = {
+        columns: Object.keys(columnMap).length,
+        imported: rowIdx,
+        skipped,
+        error_lines: errorLines,
+      };
+      if (signal.aborted) {
+        send('import.aborted', summary);
+      } else if (rowIdx === 0) {
+        send('import.error', { error: 'No data found in file', ...summary });
+      } else {
+        send('import.complete', summary);
+      }
+    } catch (err) {
+      send('import.error', {
+        error: err instanceof Error ? err.message : String(err),
+      });
+    }
+  }, user.id);
+}
@@ -0,0 +1,117 @@
+import { NextRequest } from 'next/server';
+import { Readable } from 'stream';
+import type { ReadableStream as NodeReadableStream } from 'stream/web';
+import Papa from 'papaparse';
+import { createClient } from '@/lib/supabase/server';
+import { createProgressStream } from '@/lib/utils/progress-stream';
+import { bulkUpsertCellValues } from '@/lib/supabase/queries/cells';
+import {
+  createImportColumns,
+  normalizeImportValue,
+  parseNormalization,
+  type ImportNormalization,
+} from '@/lib/utils/import-rows';
+
+export const maxDuration = 300;
+
+const BATCH_ROWS = 500;
+
+/**
+ * Imports a large CSV sent as the raw request body. The file is parsed as it
+ * streams in and written in batches, so it is never held in memory whole.
+ */
+export async function POST(request: NextRequest) {
+  const supabase = await createClient();
+  const {
+    data: { user },
+  } = await supabase.auth.getUser();
+
+  if (!user) {
+    return new Response(JSON.stringify({ error: 'Unauthorized' }), {
+      status: 401,
+    });
+  }
+
+  const { searchParams } = new URL(request.url);
+  const datasetId = searchParams.get('dataset_id');
+  if (!datasetId || !request.body) {
+    return new Response(
+      JSON.stringify({ error: 'Missing dataset_id or file body' }),
+      { status: 400 },
+    );
+  }
+
+  let normalization: ImportNormalization;
+  try {
+    normalization = parseNormalization(searchParams.get('normalize'));
+  } catch (err) {
+    return new Response(
+      JSON.stringify({ error: err instanceof Error ? err.message : String(err) }),
+      { status: 400 },
+    );
+  }
+  const body = request.body;
+
+  return createProgressStream(async ({ send, signal }) => {
+    try {
+      const parser = Papa.parse(Papa.NODE_STREAM_INPUT, {
+        header: true,
+        skipEmptyLines: true,
+      });
+      Readable.fromWeb(body as NodeReadableStream).pipe(parser);
+
+      let columnNames: string[] | null = null;
+      let columnMap: Record<string, string> = {};
+      let rowIdx = 0;
+      let batch: Parameters<typeof bulkUpsertCellValues>[1] = [];
+
+      const flush = async () => {
+        if (batch.length === 0) return;
+        await bulkUpsertCellValues(supabase, batch);
+        batch = [];
+        send('import.progress', { rows: rowIdx });
+      };
+
+      for await (const row of parser as AsyncIterable<Record<string, any>>) {
+        if (signal.aborted) break;
+        if (!columnNames) {
+          columnNames = Object.keys(row);
+          ({ columnMap } = await createImportColumns(
+            supabase,
+            datasetId,
+            columnNames,
+          ));
+          send('import.columns', { columns: columnNames.length });
+        }
+
+        for (const name of columnNames) {
+          batch.push({
+            dataset_id: datasetId,
+            column_id: columnMap[name],
+            row_idx: rowIdx,
+            value: normalizeImportValue(row[name] ?? null, normalization),
+          });
+        }
+        rowIdx++;
+
+        if (rowIdx % BATCH_ROWS === 0) {
+          await flush();
+        }
+      }
+      await flush();
+
+      if (!columnNames) {
+        send('import.error', { error: 'No data found in file' });
+        return;
+      }
+      send(signal.aborted ? 'import.aborted' : 'import.complete', {
+        columns: columnNames.length,
+        rows: rowIdx,
+      });
+    } catch (err) {
+      send('import.error', {
+        error: err instanceof Error ? err.message : String(err),
+      });
+    }
+  }, user.id);
+}
@@ -1,7 +1,11 @@
 import { NextRequest, NextResponse } from 'next/server';
 import { createClient } from '@/lib/supabase/server';
-import { createColumn, getMaxPosition } from '@/lib/supabase/queries/columns';
-import { bulkUpsertCellValues } from '@/lib/supabase/queries/cells';
+import { getDatasetColumns } from '@/lib/supabase/queries/columns';
+import {
+  importRows,
+  parseNormalization,
+  type ImportNormalization,
+} from '@/lib/utils/import-rows';
 import Papa from 'papaparse';
 import { MAX_ROWS_IMPORT } from '@/lib/types/domain';
 
@@ -19,6 +23,10 @@ export async function POST(request: NextRequest) {
   const file = formData.get('file') as File;
   const datasetId = formData.get('dataset_id') as string;
   const fileType = formData.get('file_type') as string;
+  // Optional JSON map of file header → existing column id
+  const mappingRaw = formData.get('mapping') as string | null;
+  // Optional JSON normalization options; defaults to trimming only
+  const normalizeRaw = formData.get('normalize') as string | null;
 
   if (!file || !datasetId) {
     return NextResponse.json(
@@ -27,6 +35,47 @@ export async function POST(request: NextRequest) {
     );
   }
 
+  let mapping: Record<string, string> = {};
+  if (mappingRaw) {
+    try {
+      mapping = JSON.parse(mappingRaw);
+    } catch {
+      return NextResponse.json(
+        { error: 'mapping must be a JSON object' },
+        { status: 400 },
+      );
+    }
+    if (!mapping || typeof mapping !== 'object' || Array.isArray(mapping)) {
+      return NextResponse.json(
+        { error: 'mapping must be a JSON object' },
+        { status: 400 },
+      );
+    }
+
+    const existingIds = new Set(
+      (await getDatasetColumns(supabase, datasetId)).map((c: any) => c.id),
+    );
+    const unknown = Object.values(mapping).filter(
+      (id) => !existingIds.has(id),
+    );
+    if (unknown.length > 0) {
+      return NextResponse.json(
+        { error: `Mapping references unknown columns: ${unknown.join(', ')}` },
+        { status: 400 },
+      );
+    }
+  }
+
+  let normalization: ImportNormalization;
+  try {
+    normalization = parseNormalization(normalizeRaw);
+  } catch (err) {
+    return NextResponse.json(
+      { error: err instanceof Error ? err.message : String(err) },
+      { status: 400 },
+    );
+  }
+
   const text = await file.text();
 
   let rows: Record<string, any>[];
@@ -60,39 +109,12 @@ export async function POST(request: NextRequest) {
     return NextResponse.json({ error: 'No data found in file' }, { status: 400 });
   }
 
-  // Get column names from first row
-  const columnNames = Object.keys(rows[0]);
-
-  // Create columns
-  let maxPos = await getMaxPosition(supabase, datasetId);
-  const columnMap: Record<string, string> = {};
-
-  for (const name of columnNames) {
-    maxPos++;
-    const col = await createColumn(supabase, {
-      dataset_id: datasetId,
-      name,
-      type: 'text',
-      kind: 'static',
-      position: maxPos,
-    });
-    columnMap[name] = col.id;
-  }
-
-  // Build cell values
-  const cellValues = rows.flatMap((row, rowIdx) =>
-    columnNames.map((colName) => ({
-      dataset_id: datasetId,
-      column_id: columnMap[colName],
-      row_idx: rowIdx,
-      value: row[colName] ?? null,
-    })),
+  const result = await importRows(
+    supabase,
+    datasetId,
+    rows,
+    mapping,
+    normalization,
   );
-
-  await bulkUpsertCellValues(supabase, cellValues);
-
-  return NextResponse.json({
-    columns: columnNames.length,
-    rows: rows.length,
-  });
+  return NextResponse.json(result);
 }
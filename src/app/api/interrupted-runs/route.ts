@@ -0,0 +1,168 @@
+import { NextRequest, NextResponse } from 'next/server';
+import { createClient } from '@/lib/supabase/server';
+import { getRunningOperation } from '@/lib/utils/progress-stream';
+import {
+  deleteActiveRun,
+  getActiveRun,
+  getActiveRuns,
+  toResumableRequest,
+} from '@/lib/supabase/queries/generation-runs';
+import { clearStuckGenerating } from '@/lib/supabase/queries/cell-meta';
+
+// A run updated more recently than this may still be alive on another server
+// instance, so it isn't reported as interrupted yet
+const INTERRUPTED_AFTER_MS = 60_000;
+
+const resumeRow = (run: any): number =>
+  run.last_completed_row != null
+    ? run.last_completed_row + 1
+    : run.request.offset;
+
+const resumeBody = (run: any) => ({
+  ...toResumableRequest(run.request),
+  offset: resumeRow(run),
+  limit: run.end_row - resumeRow(run),
+});
+
+/**
+ * Closes out an interrupted run: cells that were in flight when it died would
+ * otherwise spin forever.
+ */
+async function closeRun(supabase: any, run: any) {
+  await clearStuckGenerating(
+    supabase,
+    run.column_id,
+    resumeRow(run),
+    run.end_row,
+  );
+  await deleteActiveRun(supabase, run.id);
+}
+
+/**
+ * Lists generation runs that stopped without finishing, each with the body to
+ * post to /api/generate to continue after the last completed row. Read-only;
+ * resuming or dismissing a run cleans up after it.
+ */
+export async function GET(request: NextRequest) {
+  const supabase = await createClient();
+
+  const {
+    data: { user },
+  } = await supabase.auth.getUser();
+  if (!user) {
+    return NextResponse.json({ error: 'Unauthorized' }, { status: 401 });
+  }
+
+  const { searchParams } = new URL(request.url);
+  const datasetId = searchParams.get('dataset_id');
+
+  if (!datasetId) {
+    return NextResponse.json(
+      { error: 'Missing dataset_id' },
+      { status: 400 },
+    );
+  }
+
+  try {
+    const activeRuns = await getActiveRuns(supabase, datasetId);
+    const interrupted = activeRuns.filter(
+      (run: any) =>
+        !getRunningOperation(run.id) &&
+        Date.now() - new Date(run.updated_at).getTime() > INTERRUPTED_AFTER_MS,
+    );
+
+    const runs = interrupted.map((run: any) => ({
+      id: run.id,
+      column_id: run.column_id,
+      column_name: run.columns?.name,
+      started_at: run.started_at,
+      updated_at: run.updated_at,
+      last_completed_row: run.last_completed_row,
+      remaining: run.end_row - resumeRow(run),
+      resume: resumeBody(run),
+    }));
+
+    return NextResponse.json({ runs });
+  } catch (err) {
+    return NextResponse.json(
+      { error: err instanceof Error ? err.message : String(err) },
+      { status: 500 },
+    );
+  }
+}
+
+/**
+ * Takes over an interrupted run for resuming: clears its stuck cells, removes
+ * the record and returns the body to post to /api/generate, which starts the
+ * resumed run under a new id.
+ */
+export async function POST(request: NextRequest) {
+  const supabase = await createClient();
+
+  const {
+    data: { user },
+  } = await supabase.auth.getUser();
+  if (!user) {
+    return NextResponse.json({ error: 'Unauthorized' }, { status: 401 });
+  }
+
+  const { searchParams } = new URL(request.url);
+  const id = searchParams.get('id');
+
+  if (!id) {
+    return NextResponse.json({ error: 'Missing id' }, { status: 400 });
+  }
+
+  try {
+    const run = await getActiveRun(supabase, id);
+    if (!run || getRunningOperation(run.id)) {
+      return NextResponse.json(
+        { error: 'No interrupted run with this id' },
+        { status: 404 },
+      );
+    }
+
+    const resume = resumeBody(run);
+    await closeRun(supabase, run);
+    return NextResponse.json({ resume });
+  } catch (err) {
+    return NextResponse.json(
+      { error: err instanceof Error ? err.message : String(err) },
+      { status: 500 },
+    );
+  }
+}
+
+/**
+ * Dismisses an interrupted run without resuming it.
+ */
+export async function DELETE(request: NextRequest) {
+  const supabase = await createClient();
+
+  const {
+    data: { user },
+  } = await supabase.auth.getUser();
+  if (!user) {
+    return NextResponse.json({ error: 'Unauthorized' }, { status: 401 });
+  }
+
+  const { searchParams } = new URL(request.url);
+  const id = searchParams.get('id');
+
+  if (!id) {
+    return NextResponse.json({ error: 'Missing id' }, { status: 400 });
+  }
+
+  try {
+    const run = await getActiveRun(supabase, id);
+    if (run && !getRunningOperation(run.id)) {
+      await closeRun(supabase, run);
+    }
+    return NextResponse.json({ success: true });
+  } catch (err) {
+    return NextResponse.json(
+      { error: err instanceof Error ? err.message : String(err) },
+      { status: 500 },
+    );
+  }
+}
@@ -0,0 +1,33 @@
+import { NextRequest, NextResponse } from 'next/server';
+import { createClient } from '@/lib/supabase/server';
+import { getRunningOperation } from '@/lib/utils/progress-stream';
+
+export async function GET(
+  _request: NextRequest,
+  { params }: { params: Promise<{ id: string }> },
+) {
+  const supabase = await createClient();
+
+  const {
+    data: { user },
+  } = await supabase.auth.getUser();
+  if (!user) {
+    return NextResponse.json({ error: 'Unauthorized' }, { status: 401 });
+  }
+
+  const { id } = await params;
+  const operation = getRunningOperation(id);
+  // Finished and other users' operations look the same, so ids can't be probed
+  if (!operation || operation.ownerId !== user.id) {
+    return NextResponse.json(
+      { running: false, error: 'Operation not found' },
+      { status: 404 },
+    );
+  }
+
+  return NextResponse.json({
+    running: true,
+    started_at: operation.startedAt,
+    last_event: operation.lastEvent,
+  });
+}
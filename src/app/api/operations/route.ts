@@ -0,0 +1,50 @@
+import { NextRequest, NextResponse } from 'next/server';
+import { createClient } from '@/lib/supabase/server';
+import {
+  abortAllOperations,
+  abortOperation,
+} from '@/lib/utils/progress-stream';
+
+/**
+ * Emergency stop: cancels every operation the user has running on this
+ * server, or only the one given by `id`. Cells already written are kept.
+ */
+export async function DELETE(request: NextRequest) {
+  const supabase = await createClient();
+
+  const {
+    data: { user },
+  } = await supabase.auth.getUser();
+  if (!user) {
+    return NextResponse.json({ error: 'Unauthorized' }, { status: 401 });
+  }
+
+  const id = new URL(request.url).searchParams.get('id');
+  if (id) {
+    const operation = abortOperation(id, user.id);
+    if (!operation) {
+      return NextResponse.json(
+        { error: 'Operation not found' },
+        { status: 404 },
+      );
+    }
+    return NextResponse.json({
+      aborted: [
+        {
+          id: operation.id,
+          started_at: operation.startedAt,
+          last_event: operation.lastEvent,
+        },
+      ],
+    });
+  }
+
+  const aborted = abortAllOperations(user.id);
+  return NextResponse.json({
+    aborted: aborted.map((operation) => ({
+      id: operation.id,
+      started_at: operation.startedAt,
+      last_event: operation.lastEvent,
+    })),
+  });
+}
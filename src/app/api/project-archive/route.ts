@@ -0,0 +1,86 @@
+import { NextRequest, NextResponse } from 'next/server';
+import { gunzipSync, gzipSync } from 'zlib';
+import { createClient } from '@/lib/supabase/server';
+import {
+  buildProjectArchive,
+  restoreProjectArchive,
+  validateProjectArchive,
+} from '@/lib/utils/project-archive';
+
+export const maxDuration = 300;
+
+/**
+ * Downloads every dataset as one gzipped JSON archive with a manifest
+ */
+export async function GET() {
+  const supabase = await createClient();
+
+  const {
+    data: { user },
+  } = await supabase.auth.getUser();
+  if (!user) {
+    return NextResponse.json({ error: 'Unauthorized' }, { status: 401 });
+  }
+
+  try {
+    const archive = await buildProjectArchive(supabase);
+    const date = archive.manifest.exported_at.slice(0, 10);
+    return new Response(gzipSync(JSON.stringify(archive)), {
+      headers: {
+        'Content-Type': 'application/gzip',
+        'Content-Disposition': `attachment; filename="braincells-${date}.json.gz"`,
+      },
+    });
+  } catch (err) {
+    return NextResponse.json(
+      { error: err instanceof Error ? err.message : String(err) },
+      { status: 500 },
+    );
+  }
+}
+
+/**
+ * Restores an archive sent as the raw request body, gzipped or plain JSON,
+ * into new datasets
+ */
+export async function POST(request: NextRequest) {
+  const supabase = await createClient();
+
+  const {
+    data: { user },
+  } = await supabase.auth.getUser();
+  if (!user) {
+    return NextResponse.json({ error: 'Unauthorized' }, { status: 401 });
+  }
+
+  let archive;
+  try {
+    let bytes = Buffer.from(await request.arrayBuffer());
+    // gzip magic number
+    if (bytes[0] === 0x1f && bytes[1] === 0x8b) bytes = gunzipSync(bytes);
+    archive = JSON.parse(bytes.toString('utf8'));
+  } catch {
+    return NextResponse.json(
+      { error: 'Invalid archive file' },
+      { status: 400 },
+    );
+  }
+  try {
+    validateProjectArchive(archive);
+  } catch (err) {
+    return NextResponse.json(
+      { error: err instanceof Error ? err.message : String(err) },
+      { status: 400 },
+    );
+  }
+
+  try {
+    const datasetIds = await restoreProjectArchive(supabase, user.id, archive);
+    return NextResponse.json({ dataset_ids: datasetIds });
+  } catch (err) {
+    return NextResponse.json(
+      { error: err instanceof Error ? err.message : String(err) },
+      { status: 500 },
+    );
+  }
+}
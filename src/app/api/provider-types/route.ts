@@ -0,0 +1,19 @@
+import { NextResponse } from 'next/server';
+import { createClient } from '@/lib/supabase/server';
+import { PROVIDER_DESCRIPTORS } from '@/lib/types/domain';
+
+/**
+ * Describes every supported provider and the config fields it needs
+ */
+export async function GET() {
+  const supabase = await createClient();
+
+  const {
+    data: { user },
+  } = await supabase.auth.getUser();
+  if (!user) {
+    return NextResponse.json({ error: 'Unauthorized' }, { status: 401 });
+  }
+
+  return NextResponse.json({ providers: PROVIDER_DESCRIPTORS });
+}
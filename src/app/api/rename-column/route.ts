@@ -0,0 +1,37 @@
+import { NextRequest, NextResponse } from 'next/server';
+import { createClient } from '@/lib/supabase/server';
+import { renameColumn } from '@/lib/supabase/queries/columns';
+
+export async function POST(request: NextRequest) {
+  const supabase = await createClient();
+  const {
+    data: { user },
+  } = await supabase.auth.getUser();
+
+  if (!user) {
+    return NextResponse.json({ error: 'Unauthorized' }, { status: 401 });
+  }
+
+  const { column_id, name, rewrite_refs = true } = await request.json();
+  if (!column_id || !name?.trim()) {
+    return NextResponse.json(
+      { error: 'Missing column_id or name' },
+      { status: 400 },
+    );
+  }
+
+  try {
+    const rewritten = await renameColumn(
+      supabase,
+      column_id,
+      name.trim(),
+      rewrite_refs,
+    );
+    return NextResponse.json({ rewritten_prompts: rewritten });
+  } catch (err) {
+    return NextResponse.json(
+      { error: err instanceof Error ? err.message : String(err) },
+      { status: 500 },
+    );
+  }
+}
@@ -0,0 +1,36 @@
+import { NextRequest, NextResponse } from 'next/server';
+import { createClient } from '@/lib/supabase/server';
+import { restoreDatasetSnapshot } from '@/lib/supabase/queries/dataset-snapshots';
+
+/**
+ * Replaces the live dataset with a snapshot. Columns, processes and cells are
+ * restored in a single transaction.
+ */
+export async function POST(request: NextRequest) {
+  const supabase = await createClient();
+  const {
+    data: { user },
+  } = await supabase.auth.getUser();
+
+  if (!user) {
+    return NextResponse.json({ error: 'Unauthorized' }, { status: 401 });
+  }
+
+  const { snapshot_id } = await request.json();
+  if (!snapshot_id) {
+    return NextResponse.json(
+      { error: 'Missing snapshot_id' },
+      { status: 400 },
+    );
+  }
+
+  try {
+    const datasetId = await restoreDatasetSnapshot(supabase, snapshot_id);
+    return NextResponse.json({ dataset_id: datasetId });
+  } catch (err) {
+    return NextResponse.json(
+      { error: err instanceof Error ? err.message : String(err) },
+      { status: 500 },
+    );
+  }
+}
@@ -0,0 +1,80 @@
+import { NextRequest, NextResponse } from 'next/server';
+import { createClient } from '@/lib/supabase/server';
+import { getDatasetColumns } from '@/lib/supabase/queries/columns';
+import { sampleRowIndices } from '@/lib/supabase/queries/cells';
+
+const MAX_SAMPLE = 100;
+const PAGE_SIZE = 1000;
+
+/**
+ * Returns `n` random rows for spot-checking generation quality. Passing a
+ * `seed` makes the sample reproducible.
+ */
+export async function GET(request: NextRequest) {
+  const supabase = await createClient();
+
+  const {
+    data: { user },
+  } = await supabase.auth.getUser();
+  if (!user) {
+    return NextResponse.json({ error: 'Unauthorized' }, { status: 401 });
+  }
+
+  const { searchParams } = new URL(request.url);
+  const datasetId = searchParams.get('dataset_id');
+  const n = Math.min(Number(searchParams.get('n')) || 10, MAX_SAMPLE);
+  const seed = searchParams.get('seed') ?? undefined;
+
+  if (!datasetId) {
+    return NextResponse.json(
+      { error: 'Missing dataset_id' },
+      { status: 400 },
+    );
+  }
+
+  try {
+    const rowIndices = await sampleRowIndices(supabase, datasetId, n, seed);
+    const columns = await getDatasetColumns(supabase, datasetId);
+
+    // Wide datasets can exceed PostgREST's 1000-row cap, so page
+    const cells: Array<{ column_id: string; row_idx: number; value: any }> = [];
+    for (let from = 0; rowIndices.length > 0; from += PAGE_SIZE) {
+      const { data, error } = await supabase
+        .from('cell_values')
+        .select('column_id, row_idx, value')
+        .eq('dataset_id', datasetId)
+        .in('row_idx', rowIndices)
+        .order('row_idx', { ascending: true })
+        .order('column_id', { ascending: true })
+        .range(from, from + PAGE_SIZE - 1);
+
+      if (error) throw error;
+      cells.push(...data);
+      if (data.length < PAGE_SIZE) break;
+    }
+
+    const columnNames = new Map<string, string>(
+      columns.map((c: any) => [c.id, c.name]),
+    );
+    const rows = new Map<number, Record<string, any>>(
+      rowIndices.map((rowIdx) => [rowIdx, {}]),
+    );
+    for (const cell of cells) {
+      const name = columnNames.get(cell.column_id);
+      if (name) rows.get(cell.row_idx)![name] = cell.value;
+    }
+
+    return NextResponse.json({
+      columns: columns.map((c: any) => ({ id: c.id, name: c.name })),
+      rows: rowIndices.map((rowIdx) => ({
+        row_idx: rowIdx,
+        values: rows.get(rowIdx),
+      })),
+    });
+  } catch (err) {
+    return NextResponse.json(
+      { error: err instanceof Error ? err.message : String(err) },
+      { status: 500 },
+    );
+  }
+}
@@ -0,0 +1,40 @@
+import { NextResponse } from 'next/server';
+import { createClient } from '@/lib/supabase/server';
+import {
+  checkSchemaIntegrity,
+  EXPECTED_MIGRATION,
+} from '@/lib/supabase/queries/schema-check';
+
+export async function GET() {
+  const supabase = await createClient();
+
+  const {
+    data: { user },
+  } = await supabase.auth.getUser();
+  if (!user) {
+    return NextResponse.json({ error: 'Unauthorized' }, { status: 401 });
+  }
+
+  const guidance = `Database schema is behind the app; apply migrations up to ${EXPECTED_MIGRATION}`;
+
+  let result;
+  try {
+    result = await checkSchemaIntegrity(supabase);
+  } catch (err) {
+    // A probe that throws (e.g. the database is unreachable) still gets the guidance
+    return NextResponse.json(
+      {
+        ok: false,
+        expected_migration: EXPECTED_MIGRATION,
+        problems: [err instanceof Error ? err.message : String(err)],
+        error: guidance,
+      },
+      { status: 503 },
+    );
+  }
+  if (!result.ok) {
+    return NextResponse.json({ ...result, error: guidance }, { status: 503 });
+  }
+
+  return NextResponse.json(result);
+}
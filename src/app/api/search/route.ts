@@ -0,0 +1,31 @@
+import { NextRequest, NextResponse } from 'next/server';
+import { createClient } from '@/lib/supabase/server';
+import { searchDatasets } from '@/lib/supabase/queries/datasets';
+
+export async function GET(request: NextRequest) {
+  const supabase = await createClient();
+
+  const {
+    data: { user },
+  } = await supabase.auth.getUser();
+  if (!user) {
+    return NextResponse.json({ error: 'Unauthorized' }, { status: 401 });
+  }
+
+  const { searchParams } = new URL(request.url);
+  const query = searchParams.get('q')?.trim();
+
+  if (!query) {
+    return NextResponse.json({ error: 'Missing q' }, { status: 400 });
+  }
+
+  try {
+    const results = await searchDatasets(supabase, query);
+    return NextResponse.json({ results });
+  } catch (err) {
+    return NextResponse.json(
+      { error: err instanceof Error ? err.message : String(err) },
+      { status: 500 },
+    );
+  }
+}
@@ -0,0 +1,49 @@
+import { NextRequest, NextResponse } from 'next/server';
+import { createClient } from '@/lib/supabase/server';
+import { splitColumn } from '@/lib/supabase/queries/columns';
+
+export async function POST(request: NextRequest) {
+  const supabase = await createClient();
+  const {
+    data: { user },
+  } = await supabase.auth.getUser();
+
+  if (!user) {
+    return NextResponse.json({ error: 'Unauthorized' }, { status: 401 });
+  }
+
+  const {
+    column_id,
+    delimiter,
+    new_column_names,
+    overflow = 'drop',
+  } = await request.json();
+
+  if (
+    !column_id ||
+    !delimiter ||
+    !Array.isArray(new_column_names) ||
+    new_column_names.length === 0
+  ) {
+    return NextResponse.json(
+      { error: 'Missing column_id, delimiter or new_column_names' },
+      { status: 400 },
+    );
+  }
+
+  try {
+    const columnIds = await splitColumn(
+      supabase,
+      column_id,
+      delimiter,
+      new_column_names,
+      overflow,
+    );
+    return NextResponse.json({ column_ids: columnIds });
+  } catch (err) {
+    return NextResponse.json(
+      { error: err instanceof Error ? err.message : String(err) },
+      { status: 500 },
+    );
+  }
+}
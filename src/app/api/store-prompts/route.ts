@@ -0,0 +1,60 @@
+import { NextRequest, NextResponse } from 'next/server';
+import { createClient } from '@/lib/supabase/server';
+import {
+  getUserSettings,
+  upsertUserSettings,
+} from '@/lib/supabase/queries/user-settings';
+
+export async function GET() {
+  const supabase = await createClient();
+
+  const {
+    data: { user },
+  } = await supabase.auth.getUser();
+  if (!user) {
+    return NextResponse.json({ error: 'Unauthorized' }, { status: 401 });
+  }
+
+  try {
+    const settings = await getUserSettings(supabase, user.id);
+    return NextResponse.json({
+      store_prompts: settings?.store_prompts ?? false,
+    });
+  } catch (err) {
+    return NextResponse.json(
+      { error: err instanceof Error ? err.message : String(err) },
+      { status: 500 },
+    );
+  }
+}
+
+export async function POST(request: NextRequest) {
+  const supabase = await createClient();
+
+  const {
+    data: { user },
+  } = await supabase.auth.getUser();
+  if (!user) {
+    return NextResponse.json({ error: 'Unauthorized' }, { status: 401 });
+  }
+
+  const { enabled } = await request.json();
+  if (typeof enabled !== 'boolean') {
+    return NextResponse.json(
+      { error: 'enabled must be a boolean' },
+      { status: 400 },
+    );
+  }
+
+  try {
+    const settings = await upsertUserSettings(supabase, user.id, {
+      store_prompts: enabled,
+    });
+    return NextResponse.json({ store_prompts: settings.store_prompts });
+  } catch (err) {
+    return NextResponse.json(
+      { error: err instanceof Error ? err.message : String(err) },
+      { status: 500 },
+    );
+  }
+}
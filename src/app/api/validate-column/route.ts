@@ -0,0 +1,93 @@
+import { NextRequest, NextResponse } from 'next/server';
+import { createClient } from '@/lib/supabase/server';
+import { getColumn } from '@/lib/supabase/queries/columns';
+import {
+  getColumnCellValues,
+  getDatasetRowEnd,
+} from '@/lib/supabase/queries/cells';
+import {
+  getCellMetaBatch,
+  upsertCellMeta,
+} from '@/lib/supabase/queries/cell-meta';
+import { compileValidation } from '@/lib/utils/column-validation';
+
+export async function POST(request: NextRequest) {
+  const supabase = await createClient();
+  const {
+    data: { user },
+  } = await supabase.auth.getUser();
+
+  if (!user) {
+    return NextResponse.json({ error: 'Unauthorized' }, { status: 401 });
+  }
+
+  const { column_id } = await request.json();
+  if (!column_id) {
+    return NextResponse.json({ error: 'Missing column_id' }, { status: 400 });
+  }
+
+  try {
+    const column = await getColumn(supabase, column_id);
+    if (column.kind !== 'static') {
+      return NextResponse.json(
+        { error: 'Only input columns can be validated' },
+        { status: 400 },
+      );
+    }
+    if (!column.validation) {
+      return NextResponse.json(
+        { error: 'Column has no validation rules' },
+        { status: 400 },
+      );
+    }
+
+    let validate: ReturnType<typeof compileValidation>;
+    try {
+      validate = compileValidation(column.validation);
+    } catch (err) {
+      return NextResponse.json(
+        { error: err instanceof Error ? err.message : String(err) },
+        { status: 400 },
+      );
+    }
+
+    // Rows with no stored value still count, so `required` catches blanks
+    const rowEnd = await getDatasetRowEnd(supabase, column.dataset_id);
+    const cells = await getColumnCellValues(supabase, column_id);
+    const values = new Map<number, any>(
+      cells.map((c: any) => [c.row_idx, c.value]),
+    );
+    const metas = await getCellMetaBatch(supabase, column_id, 0, rowEnd);
+    const previousErrors = new Map<number, string | null>(
+      metas.map((m: any) => [m.row_idx, m.error]),
+    );
+
+    const violations: Array<{ row_idx: number; error: string }> = [];
+    for (let rowIdx = 0; rowIdx < rowEnd; rowIdx++) {
+      const error = validate(values.get(rowIdx));
+      if (error) {
+        violations.push({ row_idx: rowIdx, error });
+      }
+      // Only touch rows whose error changed, which also clears rows that
+      // have been fixed since the last check
+      if ((previousErrors.get(rowIdx) ?? null) !== error) {
+        await upsertCellMeta(supabase, {
+          column_id,
+          row_idx: rowIdx,
+          error,
+        });
+      }
+    }
+
+    return NextResponse.json({
+      checked: rowEnd,
+      invalid_rows: violations.map((v) => v.row_idx),
+      violations,
+    });
+  } catch (err) {
+    return NextResponse.json(
+      { error: err instanceof Error ? err.message : String(err) },
+      { status: 500 },
+    );
+  }
+}
@@ -0,0 +1,49 @@
+import { NextRequest, NextResponse } from 'next/server';
+import { createClient } from '@/lib/supabase/server';
+import { getDatasetColumns } from '@/lib/supabase/queries/columns';
+import { findMissingReferences } from '@/lib/utils/prompt-template';
+
+export async function GET(request: NextRequest) {
+  const supabase = await createClient();
+
+  const {
+    data: { user },
+  } = await supabase.auth.getUser();
+  if (!user) {
+    return NextResponse.json({ error: 'Unauthorized' }, { status: 401 });
+  }
+
+  const { searchParams } = new URL(request.url);
+  const datasetId = searchParams.get('dataset_id');
+
+  if (!datasetId) {
+    return NextResponse.json(
+      { error: 'Missing dataset_id' },
+      { status: 400 },
+    );
+  }
+
+  let columns;
+  try {
+    columns = await getDatasetColumns(supabase, datasetId);
+  } catch (err) {
+    return NextResponse.json(
+      { error: err instanceof Error ? err.message : String(err) },
+      { status: 500 },
+    );
+  }
+  const columnNames = columns.map((c: any) => c.name);
+  const missing: Record<string, string[]> = {};
+
+  for (const column of columns) {
+    const prompt = column.processes?.[0]?.prompt;
+    if (column.kind !== 'dynamic' || !prompt) continue;
+
+    const refs = findMissingReferences(prompt, columnNames);
+    if (refs.length > 0) {
+      missing[column.id] = refs;
+    }
+  }
+
+  return NextResponse.json({ valid: Object.keys(missing).length === 0, missing });
+}
@@ -0,0 +1,61 @@
+import { NextRequest, NextResponse } from 'next/server';
+import { createClient } from '@/lib/supabase/server';
+import { createOpenAIClient, parseExtraHeaders } from '@/lib/openai/client';
+import { checkModelAccess } from '@/lib/openai/model-access';
+import { MERCURY_BASE_URL } from '@/lib/types/domain';
+import type { Provider } from '@/lib/types/domain';
+
+/**
+ * Checks up front that the API key works and can use the given model, instead
+ * of every cell failing once generation starts. Uses the provider's model
+ * list, falling back to a one-token completion when listing isn't possible.
+ */
+export async function GET(request: NextRequest) {
+  const supabase = await createClient();
+
+  const {
+    data: { user },
+  } = await supabase.auth.getUser();
+  if (!user) {
+    return NextResponse.json({ error: 'Unauthorized' }, { status: 401 });
+  }
+
+  const apiKey = request.headers.get('x-api-key') || request.headers.get('x-openai-api-key');
+  if (!apiKey) {
+    return NextResponse.json({ error: 'Missing API key' }, { status: 401 });
+  }
+
+  const model = new URL(request.url).searchParams.get('model');
+  if (!model) {
+    return NextResponse.json({ error: 'Missing model' }, { status: 400 });
+  }
+
+  let extraHeaders: Record<string, string> | undefined;
+  try {
+    extraHeaders = parseExtraHeaders(request.headers.get('x-ai-extra-headers'));
+  } catch (err) {
+    return NextResponse.json(
+      { error: err instanceof Error ? err.message : String(err) },
+      { status: 400 },
+    );
+  }
+
+  const provider = (request.headers.get('x-ai-provider') || 'openai') as Provider;
+  const baseURL = provider === 'mercury' ? MERCURY_BASE_URL : undefined;
+  const client = createOpenAIClient(apiKey, baseURL, extraHeaders);
+
+  try {
+    const { status, message } = await checkModelAccess(client, model);
+    return NextResponse.json({
+      provider,
+      model,
+      status,
+      ...(message && { message }),
+    });
+  } catch (err) {
+    return NextResponse.json(
+      { error: err instanceof Error ? err.message : String(err) },
+      { status: 502 },
+    );
+  }
+}
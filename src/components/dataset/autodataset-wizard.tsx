@@ -33,7 +33,7 @@ export function AutoDatasetWizard({
   const [running, setRunning] = useState(false);
   const [steps, setSteps] = useState<WizardStep[]>([]);
   const [error, setError] = useState('');
-  const { apiKey, hasKey, provider } = useOpenAIKey();
+  const { hasKey, requestHeaders } = useOpenAIKey();
 
   const handleRun = async () => {
     if (!instruction.trim() || !hasKey) return;
@@ -48,8 +48,7 @@ export function AutoDatasetWizard({
         method: 'POST',
         headers: {
           'Content-Type': 'application/json',
-          'x-api-key': apiKey,
-          'x-ai-provider': provider,
+          ...requestHeaders,
         },
         body: JSON.stringify({
           instruction: instruction.trim(),
@@ -103,6 +102,19 @@ export function AutoDatasetWizard({
                 ]);
                 break;
 
+              case 'dataset.progress': {
+                const { total, completed, failed } = data.progress;
+                const percent = Math.round(((completed + failed) / total) * 100);
+                setSteps((s) =>
+                  s.map((st) =>
+                    st.event === 'populate'
+                      ? { ...st, label: `Generating initial rows... ${percent}%` }
+                      : st,
+                  ),
+                );
+                break;
+              }
+
               case 'dataset.populate.success':
                 setSteps((s) => s.map((st) => ({ ...st, done: true })));
                 if (datasetId) {
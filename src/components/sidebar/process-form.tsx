@@ -17,6 +17,8 @@ import {
   SelectValue,
 } from '@/components/ui/select';
 import { toast } from 'sonner';
+import { extractColumnReferences } from '@/lib/utils/prompt-template';
+import { PROVIDER_DESCRIPTORS } from '@/lib/types/domain';
 import type { Column, TaskType, Provider } from '@/lib/types/domain';
 
 const MODELS = [
@@ -42,12 +44,9 @@ const TASKS: { value: TaskType; label: string }[] = [
 ];
 
 // Tasks that Mercury does not support (text-only model)
-const MERCURY_UNSUPPORTED_TASKS: TaskType[] = [
-  'text-to-image',
-  'image-text-to-text',
-  'speech',
-  'transcription',
-];
+const MERCURY_UNSUPPORTED_TASKS = PROVIDER_DESCRIPTORS.find(
+  (p) => p.value === 'mercury',
+)!.unsupported_tasks;
 
 export function ProcessForm({
   column,
@@ -55,12 +54,14 @@ export function ProcessForm({
   datasetId,
   apiKey,
   provider,
+  requestHeaders,
 }: {
   column: Column;
   columns: Column[];
   datasetId: string;
   apiKey: string;
   provider: Provider;
+  requestHeaders: Record<string, string>;
 }) {
   const { updateColumnProcess, updateCell, setRowCount } =
     useDatasetStore();
@@ -80,6 +81,11 @@ export function ProcessForm({
   );
   const [generating, setGenerating] = useState(false);
   const [rowLimit, setRowLimit] = useState(5);
+  const [interruptedRun, setInterruptedRun] = useState<{
+    id: string;
+    column_id: string;
+    remaining: number;
+  } | null>(null);
 
   const availableModels = MODELS.filter((m) => m.provider === provider);
 
@@ -114,16 +120,16 @@ export function ProcessForm({
     setPrompt((prev) => prev + '{{' + colName + '}}');
   };
 
+  // Includes columns referenced through template functions like {{upper:Name}}
+  const getReferencedColumnIds = () =>
+    extractColumnReferences(
+      prompt,
+      columns.map((c) => c.name),
+    ).map((name) => columns.find((c) => c.name === name)!.id);
+
   const handleSave = async () => {
     const supabase = createClient();
-    const refRegex = /\{\{([^}]+)\}\}/g;
-    const refs: string[] = [];
-    let m;
-    while ((m = refRegex.exec(prompt)) !== null) {
-      const refName = m[1].trim();
-      const refCol = columns.find((c) => c.name === refName);
-      if (refCol) refs.push(refCol.id);
-    }
+    const refs = getReferencedColumnIds();
 
     const { data, error } = await supabase
       .from('processes')
@@ -133,6 +139,7 @@ export function ProcessForm({
           prompt,
           model,
           task,
+          provider,
           search_enabled: searchEnabled,
           image_column_id: imageColumnId || null,
         },
@@ -167,6 +174,7 @@ export function ProcessForm({
       prompt,
       model,
       task,
+      provider,
       search_enabled: searchEnabled,
       image_column_id: imageColumnId || undefined,
       columns_references: refs,
@@ -175,7 +183,7 @@ export function ProcessForm({
     toast.success('Process saved');
   };
 
-  const handleGenerate = async () => {
+  const handleGenerate = async (onlyStale = false) => {
     if (!apiKey) {
       toast.error('Set your API key in Settings');
       return;
@@ -183,42 +191,83 @@ export function ProcessForm({
 
     await handleSave();
 
+    const refs = getReferencedColumnIds();
+
+    await runGeneration({
+      dataset_id: datasetId,
+      column_id: column.id,
+      column_name: column.name,
+      process: {
+        prompt,
+        model,
+        task,
+        search_enabled: searchEnabled,
+        image_column_id: imageColumnId || undefined,
+        columns_references: refs,
+      },
+      offset: 0,
+      limit: rowLimit,
+      only_stale: onlyStale,
+    });
+  };
+
+  // Offer to pick up a run on this column that was cut off by a restart
+  useEffect(() => {
+    let cancelled = false;
+    fetch(`/api/interrupted-runs?dataset_id=${datasetId}`)
+      .then((res) => (res.ok ? res.json() : { runs: [] }))
+      .then(({ runs }) => {
+        if (cancelled) return;
+        setInterruptedRun(
+          runs.find((r: any) => r.column_id === column.id) ?? null,
+        );
+      })
+      .catch(() => {});
+    return () => {
+      cancelled = true;
+    };
+  }, [datasetId, column.id]);
+
+  const handleResume = async () => {
+    if (!interruptedRun) return;
+    if (!apiKey) {
+      toast.error('Set your API key in Settings');
+      return;
+    }
+
+    const res = await fetch(`/api/interrupted-runs?id=${interruptedRun.id}`, {
+      method: 'POST',
+    });
+    setInterruptedRun(null);
+    if (!res.ok) {
+      toast.error('The interrupted run could not be resumed');
+      return;
+    }
+    const { resume } = await res.json();
+    await runGeneration(resume);
+  };
+
+  const handleDismiss = () => {
+    if (!interruptedRun) return;
+    fetch(`/api/interrupted-runs?id=${interruptedRun.id}`, {
+      method: 'DELETE',
+    });
+    setInterruptedRun(null);
+  };
+
+  const runGeneration = async (body: Record<string, any>) => {
     setGenerating(true);
     setIsGenerating(true);
     setGeneratingColumnId(column.id);
 
-    const refRegex = /\{\{([^}]+)\}\}/g;
-    const refs: string[] = [];
-    let m;
-    while ((m = refRegex.exec(prompt)) !== null) {
-      const refName = m[1].trim();
-      const refCol = columns.find((c) => c.name === refName);
-      if (refCol) refs.push(refCol.id);
-    }
-
     try {
       const res = await fetch('/api/generate', {
         method: 'POST',
         headers: {
           'Content-Type': 'application/json',
-          'x-api-key': apiKey,
-          'x-ai-provider': provider,
+          ...requestHeaders,
         },
-        body: JSON.stringify({
-          dataset_id: datasetId,
-          column_id: column.id,
-          column_name: column.name,
-          process: {
-            prompt,
-            model,
-            task,
-            search_enabled: searchEnabled,
-            image_column_id: imageColumnId || undefined,
-            columns_references: refs,
-          },
-          offset: 0,
-          limit: rowLimit,
-        }),
+        body: JSON.stringify(body),
       });
 
       if (!res.ok || !res.body) {
@@ -259,6 +308,7 @@ export function ProcessForm({
                 row_idx: data.row_idx,
                 value: data.value,
                 error: data.error,
+                error_status: data.error_status,
                 generating: false,
                 validated: false,
                 sources: data.sources,
@@ -269,6 +319,10 @@ export function ProcessForm({
               }
             }
 
+            if (data.event === 'generation.stale') {
+              toast.info(`${data.stale} of ${data.checked} cells are stale`);
+            }
+
             if (data.event === 'generation.error') {
               toast.error(data.error);
             }
@@ -457,13 +511,44 @@ export function ProcessForm({
         <Button
           size="sm"
           className="flex-1 text-xs"
-          onClick={handleGenerate}
+          onClick={() => handleGenerate()}
           disabled={generating || !prompt.trim()}
         >
           {generating ? 'Generating...' : 'Generate'}
         </Button>
+        <Button
+          variant="outline"
+          size="sm"
+          className="text-xs"
+          onClick={() => handleGenerate(true)}
+          disabled={generating || !prompt.trim()}
+        >
+          Update stale
+        </Button>
       </div>
 
+      {interruptedRun?.column_id === column.id && !generating && (
+        <div className="space-y-2 rounded-md bg-amber-900/50 p-3 text-xs text-amber-300">
+          <p>
+            Generation was interrupted with {interruptedRun.remaining} rows
+            left
+          </p>
+          <div className="flex gap-2">
+            <Button size="sm" className="text-xs" onClick={handleResume}>
+              Resume
+            </Button>
+            <Button
+              variant="ghost"
+              size="sm"
+              className="text-xs text-zinc-400"
+              onClick={handleDismiss}
+            >
+              Dismiss
+            </Button>
+          </div>
+        </div>
+      )}
+
       {generating && (
         <div className="text-xs text-zinc-500 animate-pulse">
           Generating cells... This may take a moment.
@@ -7,6 +7,8 @@ import { useDatasetStore } from '@/lib/store/dataset-store';
 import { upsertCellValue } from '@/lib/supabase/queries/cells';
 import { createClient } from '@/lib/supabase/client';
 import { toast } from 'sonner';
+import { isOversized } from '@/lib/utils/cell-size';
+import { MAX_CELL_BYTES } from '@/lib/types/domain';
 import type { TaskType } from '@/lib/types/domain';
 
 export function EditableCell({
@@ -67,6 +69,11 @@ export function EditableCell({
     // Skip if unchanged
     if (newValue === (value != null ? String(value) : null)) return;
 
+    if (newValue && isOversized(newValue)) {
+      toast.error(`Value exceeds the ${MAX_CELL_BYTES}-byte cell limit`);
+      return;
+    }
+
     // Optimistic update
     updateCell(columnId, { row_idx: rowIdx, value: newValue, generating: false, validated: false });
 
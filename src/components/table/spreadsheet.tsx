@@ -29,7 +29,7 @@ export function Spreadsheet({
     mergeCells,
   } = useDatasetStore();
   const { sidebarOpen, selectedColumnId } = useUIStore();
-  const { apiKey, hasKey, provider } = useOpenAIKey();
+  const { apiKey, hasKey, provider, requestHeaders } = useOpenAIKey();
   const [initialLoad, setInitialLoad] = useState(true);
 
   useRealtimeCells(dataset.id);
@@ -58,6 +58,7 @@ export function Spreadsheet({
             prompt: col.processes[0].prompt,
             model: col.processes[0].model,
             task: col.processes[0].task,
+            provider: col.processes[0].provider ?? undefined,
             search_enabled: col.processes[0].search_enabled,
             image_column_id: col.processes[0].image_column_id,
           }
@@ -197,6 +198,7 @@ export function Spreadsheet({
               datasetId={dataset.id}
               apiKey={apiKey}
               provider={provider}
+              requestHeaders={requestHeaders}
             />
           </div>
         )}
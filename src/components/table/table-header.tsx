@@ -2,7 +2,7 @@
 
 import { useUIStore } from '@/lib/store/ui-store';
 import { useDatasetStore } from '@/lib/store/dataset-store';
-import { createClient } from '@/lib/supabase/client';
+import { toast } from 'sonner';
 import { Badge } from '@/components/ui/badge';
 import {
   DropdownMenu,
@@ -23,15 +23,44 @@ export function TableHeader({
   const { setSelectedColumnId, selectedColumnId } = useUIStore();
   const { removeColumn } = useDatasetStore();
 
-  const handleDelete = async (col: Column) => {
-    const supabase = createClient();
-    await supabase.from('columns').delete().eq('id', col.id);
+  const deleteColumn = async (col: Column) => {
+    const res = await fetch('/api/delete-column', {
+      method: 'POST',
+      headers: { 'Content-Type': 'application/json' },
+      body: JSON.stringify({ column_id: col.id }),
+    });
+    if (!res.ok) {
+      toast.error('Failed to delete column');
+      return;
+    }
     removeColumn(col.id);
     if (selectedColumnId === col.id) {
       setSelectedColumnId(null);
     }
   };
 
+  const handleDelete = async (col: Column) => {
+    // Dry run first so we can warn before breaking prompts that use it
+    const res = await fetch('/api/delete-column', {
+      method: 'POST',
+      headers: { 'Content-Type': 'application/json' },
+      body: JSON.stringify({ column_id: col.id, dry_run: true }),
+    });
+    const { dependents = [] } = res.ok ? await res.json() : {};
+
+    if (dependents.length > 0) {
+      toast.warning(
+        `Referenced by ${dependents.map((d: { name: string }) => d.name).join(', ')}`,
+        {
+          action: { label: 'Delete anyway', onClick: () => deleteColumn(col) },
+        },
+      );
+      return;
+    }
+
+    await deleteColumn(col);
+  };
+
   const visibleColumns = columns.filter((c) => c.visible);
 
   return (
@@ -1,16 +1,20 @@
 'use client';
 
-import { useState, useEffect, useCallback } from 'react';
+import { useState, useEffect, useCallback, useMemo } from 'react';
 import type { Provider } from '@/lib/types/domain';
 import { MERCURY_BASE_URL } from '@/lib/types/domain';
 
 const PROVIDER_KEY = 'ai-provider';
 const API_KEY_KEY = 'ai-api-key';
 const LEGACY_KEY = 'openai-api-key';
+const EXTRA_HEADERS_KEY = 'ai-extra-headers';
 
 export function useOpenAIKey() {
   const [provider, setProviderState] = useState<Provider>('openai');
   const [apiKey, setApiKeyState] = useState<string>('');
+  const [extraHeaders, setExtraHeadersState] = useState<Record<string, string>>(
+    {},
+  );
   const [isLoaded, setIsLoaded] = useState(false);
 
   useEffect(() => {
@@ -30,6 +34,15 @@ export function useOpenAIKey() {
       if (storedProvider) setProviderState(storedProvider);
       if (storedKey) setApiKeyState(storedKey);
     }
+
+    const storedHeaders = localStorage.getItem(EXTRA_HEADERS_KEY);
+    if (storedHeaders) {
+      try {
+        setExtraHeadersState(JSON.parse(storedHeaders));
+      } catch {
+        localStorage.removeItem(EXTRA_HEADERS_KEY);
+      }
+    }
     setIsLoaded(true);
   }, []);
 
@@ -48,14 +61,38 @@ export function useOpenAIKey() {
     setApiKeyState('');
   }, []);
 
+  const setExtraHeaders = useCallback((headers: Record<string, string>) => {
+    if (Object.keys(headers).length > 0) {
+      localStorage.setItem(EXTRA_HEADERS_KEY, JSON.stringify(headers));
+    } else {
+      localStorage.removeItem(EXTRA_HEADERS_KEY);
+    }
+    setExtraHeadersState(headers);
+  }, []);
+
   const baseURL = provider === 'mercury' ? MERCURY_BASE_URL : undefined;
 
+  // Headers to send with every request to our generation API routes
+  const requestHeaders = useMemo(
+    () => ({
+      'x-api-key': apiKey,
+      'x-ai-provider': provider,
+      ...(Object.keys(extraHeaders).length > 0 && {
+        'x-ai-extra-headers': JSON.stringify(extraHeaders),
+      }),
+    }),
+    [apiKey, provider, extraHeaders],
+  );
+
   return {
     provider,
     setProvider,
     apiKey,
     setApiKey,
     clearApiKey,
+    extraHeaders,
+    setExtraHeaders,
+    requestHeaders,
     isLoaded,
     hasKey: !!apiKey,
     baseURL,
@@ -0,0 +1,103 @@
+import type { Provider } from '@/lib/types/domain';
+import type { ProviderErrorKind } from './errors';
+
+const FAILURE_THRESHOLD = 5;
+const FAILURE_WINDOW_MS = 60_000;
+const COOLDOWN_MS = 30_000;
+
+export const CIRCUIT_OPEN_ERROR = 'Provider circuit open';
+
+type CircuitState = 'closed' | 'open' | 'half-open';
+
+interface Breaker {
+  state: CircuitState;
+  failures: number[];
+  openedAt: number;
+  probing: boolean;
+}
+
+// Breaker state per credential and provider on this server instance, so one
+// user's failing key never trips the circuit for everyone else
+const breakers = new Map<string, Breaker>();
+
+function getBreaker(scope: string, provider: Provider): Breaker {
+  const key = `${scope}:${provider}`;
+  let breaker = breakers.get(key);
+  if (!breaker) {
+    breaker = { state: 'closed', failures: [], openedAt: 0, probing: false };
+    breakers.set(key, breaker);
+  }
+  return breaker;
+}
+
+/**
+ * Whether a failed cell says anything about the provider's health. Local
+ * failures (prompt too long, cell timeout, missing input) and requests the
+ * provider rejected on their merits don't count.
+ */
+export function isProviderFailure(result: {
+  error_kind?: ProviderErrorKind;
+  error_status?: number;
+}): boolean {
+  if (result.error_kind === 'connection') return true;
+  return (
+    (result.error_kind === 'server' ||
+      result.error_kind === 'unknown' ||
+      result.error_kind === 'rate_limit') &&
+    result.error_status !== undefined
+  );
+}
+
+/**
+ * Whether a request to the provider may go out. Once the cooldown has passed
+ * an open circuit lets a single probe request through to test recovery.
+ */
+export function canAttempt(scope: string, provider: Provider): boolean {
+  const breaker = getBreaker(scope, provider);
+  if (breaker.state === 'closed') return true;
+
+  if (breaker.state === 'open') {
+    if (Date.now() - breaker.openedAt < COOLDOWN_MS) return false;
+    breaker.state = 'half-open';
+  }
+
+  if (breaker.probing) return false;
+  breaker.probing = true;
+  return true;
+}
+
+export function recordSuccess(scope: string, provider: Provider) {
+  const breaker = getBreaker(scope, provider);
+  breaker.state = 'closed';
+  breaker.failures = [];
+  breaker.probing = false;
+}
+
+/**
+ * Frees a half-open circuit's probe after an attempt that told us nothing
+ * about the provider, so the next request can probe instead
+ */
+export function releaseAttempt(scope: string, provider: Provider) {
+  getBreaker(scope, provider).probing = false;
+}
+
+export function recordFailure(scope: string, provider: Provider) {
+  const breaker = getBreaker(scope, provider);
+  const now = Date.now();
+
+  if (breaker.state === 'half-open') {
+    breaker.state = 'open';
+    breaker.openedAt = now;
+    breaker.probing = false;
+    return;
+  }
+
+  breaker.failures = breaker.failures
+    .filter((at) => now - at < FAILURE_WINDOW_MS)
+    .concat(now);
+  if (breaker.failures.length >= FAILURE_THRESHOLD) {
+    breaker.state = 'open';
+    breaker.openedAt = now;
+    breaker.failures = [];
+  }
+}
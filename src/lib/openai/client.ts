@@ -1,5 +1,80 @@
+import { createHash } from 'crypto';
 import OpenAI from 'openai';
+import { APP_VERSION } from '@/lib/version';
 
-export function createOpenAIClient(apiKey: string, baseURL?: string): OpenAI {
-  return new OpenAI({ apiKey, ...(baseURL && { baseURL }) });
+const HEADER_NAME_PATTERN = /^[!#$%&'*+\-.^_`|~0-9A-Za-z]+$/;
+const HEADER_VALUE_PATTERN = /^[^\r\n\0]*$/;
+
+// Identifies the app to gateways; a custom User-Agent header overrides it
+export const USER_AGENT = `BrainCells/${APP_VERSION}`;
+
+/**
+ * Headers this app sends with every provider request, on top of what the SDK
+ * adds itself
+ */
+export function buildDefaultHeaders(
+  extraHeaders?: Record<string, string>,
+): Record<string, string> {
+  return { 'User-Agent': USER_AGENT, ...extraHeaders };
+}
+
+// The SDK retries with the same key; a deliberate retry gets a new attempt
+export function idempotencyHeaders(
+  key: string,
+  attempt: number,
+): Record<string, string> {
+  return { 'Idempotency-Key': `${key}:${attempt}` };
+}
+
+/**
+ * Stable, non-reversible id for an API key, so per-credential server state
+ * never holds the key itself
+ */
+export function credentialScope(apiKey: string): string {
+  return createHash('sha256').update(apiKey).digest('hex').slice(0, 16);
+}
+
+export function createOpenAIClient(
+  apiKey: string,
+  baseURL?: string,
+  extraHeaders?: Record<string, string>,
+): OpenAI {
+  return new OpenAI({
+    apiKey,
+    ...(baseURL && { baseURL }),
+    defaultHeaders: buildDefaultHeaders(extraHeaders),
+  });
+}
+
+/**
+ * Parses the JSON-encoded `x-ai-extra-headers` request header into a map of
+ * headers to forward to the provider. Throws on malformed names or values.
+ */
+export function parseExtraHeaders(
+  raw: string | null,
+): Record<string, string> | undefined {
+  if (!raw) return undefined;
+
+  let parsed: unknown;
+  try {
+    parsed = JSON.parse(raw);
+  } catch {
+    throw new Error('Custom headers must be a JSON object');
+  }
+  if (!parsed || typeof parsed !== 'object' || Array.isArray(parsed)) {
+    throw new Error('Custom headers must be a JSON object');
+  }
+
+  const headers: Record<string, string> = {};
+  for (const [name, value] of Object.entries(parsed)) {
+    if (!HEADER_NAME_PATTERN.test(name)) {
+      throw new Error(`Invalid custom header name: ${name}`);
+    }
+    if (typeof value !== 'string' || !HEADER_VALUE_PATTERN.test(value)) {
+      throw new Error(`Invalid value for custom header: ${name}`);
+    }
+    headers[name] = value;
+  }
+
+  return Object.keys(headers).length > 0 ? headers : undefined;
 }
@@ -0,0 +1,71 @@
+import OpenAI from 'openai';
+
+export type ProviderErrorKind =
+  | 'auth'
+  | 'rate_limit'
+  | 'quota'
+  | 'context_length'
+  | 'invalid_request'
+  | 'server'
+  | 'connection'
+  | 'timeout'
+  | 'unknown';
+
+// A run stops at the first quota error; every later request would fail too
+export const QUOTA_EXCEEDED_ERROR = 'Provider quota exceeded';
+
+export interface ProviderError {
+  kind: ProviderErrorKind;
+  message: string;
+  // HTTP status of the provider response, absent for connection failures
+  status?: number;
+}
+
+/**
+ * Maps an error thrown by the OpenAI SDK to a stable kind using the parsed
+ * `error.type`/`error.code` of the response body, falling back to the HTTP
+ * status. Mercury returns the same error shape.
+ */
+export function classifyProviderError(err: unknown): ProviderError {
+  const message = err instanceof Error ? err.message : String(err);
+
+  if (err instanceof OpenAI.APIConnectionError) {
+    return { kind: 'connection', message };
+  }
+  if (!(err instanceof OpenAI.APIError)) {
+    return { kind: 'unknown', message };
+  }
+
+  const code = err.code ?? '';
+  const type = err.type ?? '';
+  const status = err.status;
+  const result = (kind: ProviderErrorKind): ProviderError => ({
+    kind,
+    message,
+    ...(status !== undefined && { status }),
+  });
+
+  if (code === 'context_length_exceeded') {
+    return result('context_length');
+  }
+  if (code === 'insufficient_quota' || type === 'insufficient_quota') {
+    return result('quota');
+  }
+  if (
+    err.status === 401 ||
+    code === 'invalid_api_key' ||
+    type === 'authentication_error'
+  ) {
+    return result('auth');
+  }
+  if (err.status === 429 || code === 'rate_limit_exceeded') {
+    return result('rate_limit');
+  }
+  if (err.status === 400 || type === 'invalid_request_error') {
+    return result('invalid_request');
+  }
+  if (err.status !== undefined && err.status >= 500) {
+    return result('server');
+  }
+  return result('unknown');
+}
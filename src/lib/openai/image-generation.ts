@@ -4,6 +4,7 @@ export async function generateImage(
   client: OpenAI,
   prompt: string,
   model = 'gpt-image-1',
+  signal?: AbortSignal,
 ): Promise<{ value?: string; error?: string }> {
   try {
     const response = await client.images.generate({
@@ -12,7 +13,7 @@ export async function generateImage(
       n: 1,
       size: '1024x1024',
       response_format: 'b64_json',
-    });
+    }, { signal });
 
     const imageUrl = response.data?.[0]?.url;
     const b64 = response.data?.[0]?.b64_json;
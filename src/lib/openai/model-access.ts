@@ -0,0 +1,66 @@
+import OpenAI from 'openai';
+import { classifyProviderError } from './errors';
+
+export type ModelAccess = 'accessible' | 'model_inaccessible' | 'key_invalid';
+
+export interface ModelAccessResult {
+  status: ModelAccess;
+  message?: string;
+}
+
+const LIST_TIMEOUT_MS = 10_000;
+const PROBE_TIMEOUT_MS = 20_000;
+
+/**
+ * Checks that the client's key works and can use the model, from the
+ * provider's model list, falling back to a one-token completion when listing
+ * isn't possible. Throws for failures that say nothing about access, such as
+ * a connection error.
+ */
+export async function checkModelAccess(
+  client: OpenAI,
+  model: string,
+): Promise<ModelAccessResult> {
+  try {
+    const models = await client.models.list({
+      maxRetries: 0,
+      timeout: LIST_TIMEOUT_MS,
+    });
+    const ids = models.data.map((m) => m.id);
+    if (ids.length > 0) {
+      return ids.includes(model)
+        ? { status: 'accessible' }
+        : {
+            status: 'model_inaccessible',
+            message: `${model} is not available to this key`,
+          };
+    }
+  } catch (err) {
+    const { kind, message } = classifyProviderError(err);
+    if (kind === 'auth') return { status: 'key_invalid', message };
+    if (kind === 'connection') throw err;
+    // Otherwise the provider may not support listing; try the model directly
+  }
+
+  try {
+    await client.chat.completions.create(
+      {
+        model,
+        messages: [{ role: 'user', content: 'ping' }],
+        max_tokens: 1,
+      },
+      { maxRetries: 0, timeout: PROBE_TIMEOUT_MS },
+    );
+    return { status: 'accessible' };
+  } catch (err) {
+    const { kind, message } = classifyProviderError(err);
+    if (kind === 'auth') return { status: 'key_invalid', message };
+    if (
+      err instanceof OpenAI.APIError &&
+      (err.status === 403 || err.status === 404 || err.code === 'model_not_found')
+    ) {
+      return { status: 'model_inaccessible', message };
+    }
+    throw err;
+  }
+}
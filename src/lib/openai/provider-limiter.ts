@@ -0,0 +1,69 @@
+import type { Provider } from '@/lib/types/domain';
+import { PROVIDER_MAX_CONCURRENT_REQUESTS } from '@/lib/types/domain';
+
+interface Semaphore {
+  active: number;
+  waiting: (() => void)[];
+}
+
+// Shared by every run on this server instance, keyed by credential and
+// provider so one user's runs never queue behind another's
+const semaphores = new Map<string, Semaphore>();
+
+function getSemaphore(key: string): Semaphore {
+  let semaphore = semaphores.get(key);
+  if (!semaphore) {
+    semaphore = { active: 0, waiting: [] };
+    semaphores.set(key, semaphore);
+  }
+  return semaphore;
+}
+
+/**
+ * Runs `task` once one of the provider's request slots for this credential is
+ * free. A run's own MAX_CONCURRENCY bounds how many cells it starts at once;
+ * this bounds the requests of all concurrent runs together, so two columns
+ * generating at the same time can't exceed what the provider tolerates. A
+ * waiter whose `signal` aborts leaves the queue and rejects.
+ */
+export async function withProviderSlot<T>(
+  scope: string,
+  provider: Provider,
+  task: () => Promise<T>,
+  signal?: AbortSignal,
+): Promise<T> {
+  const key = `${scope}:${provider}`;
+  const semaphore = getSemaphore(key);
+  if (semaphore.active >= PROVIDER_MAX_CONCURRENT_REQUESTS[provider]) {
+    await new Promise<void>((resolve, reject) => {
+      if (signal?.aborted) {
+        reject(new Error('Aborted while waiting for a provider slot'));
+        return;
+      }
+      const onAbort = () => {
+        semaphore.waiting.splice(semaphore.waiting.indexOf(waiter), 1);
+        reject(new Error('Aborted while waiting for a provider slot'));
+      };
+      const waiter = () => {
+        signal?.removeEventListener('abort', onAbort);
+        resolve();
+      };
+      semaphore.waiting.push(waiter);
+      signal?.addEventListener('abort', onAbort, { once: true });
+    });
+  } else {
+    semaphore.active++;
+  }
+
+  try {
+    return await task();
+  } finally {
+    // Hand the slot straight to the next waiter, or give it back
+    const next = semaphore.waiting.shift();
+    if (next) {
+      next();
+    } else if (--semaphore.active === 0) {
+      semaphores.delete(key);
+    }
+  }
+}
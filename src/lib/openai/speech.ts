@@ -7,13 +7,14 @@ export async function generateSpeech(
   text: string,
   voice: Voice = 'alloy',
   model: 'tts-1' | 'tts-1-hd' = 'tts-1',
+  signal?: AbortSignal,
 ): Promise<{ value?: ArrayBuffer; error?: string }> {
   try {
     const response = await client.audio.speech.create({
       model,
       voice,
       input: text,
-    });
+    }, { signal });
 
     const buffer = await response.arrayBuffer();
     return { value: buffer };
@@ -0,0 +1,22 @@
+import type { ChatCompletionMessageParam } from 'openai/resources/chat/completions';
+
+/**
+ * Mandatory preamble configured by the deployment (e.g. compliance rules).
+ * Read from the server environment so it cannot be overridden per column.
+ */
+export function getGlobalSystemPrefix(): string | undefined {
+  return process.env.GLOBAL_SYSTEM_PREFIX?.trim() || undefined;
+}
+
+export function applySystemPrefix(
+  messages: ChatCompletionMessageParam[],
+): ChatCompletionMessageParam[] {
+  const prefix = getGlobalSystemPrefix();
+  if (!prefix) return messages;
+
+  const [first, ...rest] = messages;
+  if (first?.role === 'system' && typeof first.content === 'string') {
+    return [{ role: 'system', content: `${prefix}\n\n${first.content}` }, ...rest];
+  }
+  return [{ role: 'system', content: prefix }, ...messages];
+}
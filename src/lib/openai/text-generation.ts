@@ -1,24 +1,168 @@
 import OpenAI from 'openai';
-import type { ChatCompletionCreateParamsNonStreaming, ChatCompletionCreateParamsStreaming } from 'openai/resources/chat/completions';
+import type {
+  ChatCompletionCreateParamsNonStreaming,
+  ChatCompletionCreateParamsStreaming,
+  ChatCompletionTokenLogprob,
+  ChatCompletionTool,
+} from 'openai/resources/chat/completions';
+import { applySystemPrefix } from './system-prompt';
+import { idempotencyHeaders } from './client';
+import { classifyProviderError, type ProviderErrorKind } from './errors';
+import type { ChatMessage } from '@/lib/types/domain';
+
+export type ReasoningEffort = 'low' | 'medium' | 'high';
+
+export interface ChatOptions {
+  logprobs?: boolean;
+  top_logprobs?: number;
+  reasoning_effort?: ReasoningEffort;
+  force_json?: boolean;
+  greedy?: boolean;
+  tools?: ChatCompletionTool[];
+  // Sent as `Idempotency-Key` so the SDK's network retries of one request
+  // aren't generated twice. OpenAI honors it; other providers ignore it.
+  idempotency_key?: string;
+  // Cancels the in-flight request
+  signal?: AbortSignal;
+}
+
+// Only OpenAI reasoning models accept `reasoning_effort`
+const supportsReasoningEffort = (model: string) =>
+  /^o\d/.test(model) || model.startsWith('gpt-5');
+
+export const JSON_MODE_INSTRUCTION =
+  'Respond with only valid JSON, no prose or code fences.';
+
+const withJsonInstruction = (messages: ChatMessage[]): ChatMessage[] => {
+  const lastUser = messages.map((m) => m.role).lastIndexOf('user');
+  if (lastUser < 0) {
+    return [...messages, { role: 'user', content: JSON_MODE_INSTRUCTION }];
+  }
+  return messages.map((m, i) =>
+    i === lastUser
+      ? { ...m, content: `${m.content}\n\n${JSON_MODE_INSTRUCTION}` }
+      : m,
+  );
+};
+
+const parseJsonOutput = (content: string): string | null => {
+  const stripped = content
+    .trim()
+    .replace(/^```(?:json)?\s*/i, '')
+    .replace(/\s*```$/, '');
+  try {
+    JSON.parse(stripped);
+    return stripped;
+  } catch {
+    return null;
+  }
+};
+
+// A tool the model asked to call; the caller decides how to run it
+export interface ToolCall {
+  name: string;
+  arguments: string;
+}
+
+export interface ChatResult {
+  value?: string;
+  error?: string;
+  error_kind?: ProviderErrorKind;
+  error_status?: number;
+  logprobs?: ChatCompletionTokenLogprob[];
+  tool_calls?: ToolCall[];
+}
 
 export async function generateText(
   client: OpenAI,
   prompt: string,
   model = 'gpt-4o-mini',
-): Promise<{ value?: string; error?: string }> {
+  options: ChatOptions = {},
+): Promise<ChatResult> {
+  return generateChat(client, [{ role: 'user', content: prompt }], model, options);
+}
+
+export function buildChatParams(
+  messages: ChatMessage[],
+  model: string,
+  options: ChatOptions = {},
+): ChatCompletionCreateParamsNonStreaming {
+  const isMercury = model.startsWith('mercury');
+  return {
+    model,
+    // The instruction is also required by OpenAI's native JSON mode
+    messages: applySystemPrefix(
+      options.force_json ? withJsonInstruction(messages) : messages,
+    ),
+    ...(isMercury && { realtime: true } as any),
+    // Mercury has no native JSON mode and relies on the instruction alone
+    ...(options.force_json &&
+      !isMercury && { response_format: { type: 'json_object' } }),
+    // Mercury does not return logprobs
+    ...(options.logprobs && !isMercury && {
+      logprobs: true,
+      ...(options.top_logprobs && { top_logprobs: options.top_logprobs }),
+    }),
+    // Greedy decoding: temperature 0 with nucleus sampling disabled on OpenAI
+    // and Mercury. Reasoning models reject sampling params, so it's a no-op.
+    ...(options.greedy &&
+      !supportsReasoningEffort(model) && { temperature: 0, top_p: 1 }),
+    ...(options.tools?.length && { tools: options.tools }),
+    ...(options.reasoning_effort &&
+      supportsReasoningEffort(model) && {
+        reasoning_effort: options.reasoning_effort,
+      }),
+  };
+}
+
+export async function generateChat(
+  client: OpenAI,
+  messages: ChatMessage[],
+  model = 'gpt-4o-mini',
+  options: ChatOptions = {},
+): Promise<ChatResult> {
   try {
-    const params: ChatCompletionCreateParamsNonStreaming = {
-      model,
-      messages: [{ role: 'user', content: prompt }],
-      ...(model.startsWith('mercury') && { realtime: true } as any),
-    };
-    const response = await client.chat.completions.create(params);
-
-    const content = response.choices[0]?.message?.content;
-    return { value: content || '' };
+    const params = buildChatParams(messages, model, options);
+    // In JSON mode, retry once when the output doesn't parse
+    const attempts = options.force_json ? 2 : 1;
+
+    for (let attempt = 1; ; attempt++) {
+      const response = await client.chat.completions.create(params, {
+        ...(options.idempotency_key && {
+          // A JSON retry must be a new request, not a replay
+          headers: idempotencyHeaders(options.idempotency_key, attempt),
+        }),
+        ...(options.signal && { signal: options.signal }),
+      });
+      const choice = response.choices[0];
+      let value = choice?.message?.content || '';
+
+      const toolCalls = (choice?.message?.tool_calls ?? []).flatMap((call) =>
+        call.type === 'function'
+          ? [{ name: call.function.name, arguments: call.function.arguments }]
+          : [],
+      );
+      if (toolCalls.length > 0) {
+        return { value, tool_calls: toolCalls };
+      }
+
+      if (options.force_json) {
+        const json = parseJsonOutput(value);
+        if (json === null) {
+          if (attempt < attempts) continue;
+          return { error: 'Model response is not valid JSON' };
+        }
+        value = json;
+      }
+
+      return {
+        value,
+        ...(choice?.logprobs?.content && { logprobs: choice.logprobs.content }),
+      };
+    }
   } catch (err) {
-    const message = err instanceof Error ? err.message : String(err);
-    return { error: message };
+    const { kind, message, status } = classifyProviderError(err);
+    return { error: message, error_kind: kind, error_status: status };
   }
 }
 
@@ -29,7 +173,7 @@ export async function* streamText(
 ): AsyncGenerator<{ value: string; done: boolean }> {
   const params: ChatCompletionCreateParamsStreaming = {
     model,
-    messages: [{ role: 'user', content: prompt }],
+    messages: applySystemPrefix([{ role: 'user', content: prompt }]),
     stream: true,
     ...(model.startsWith('mercury') && { realtime: true } as any),
   };
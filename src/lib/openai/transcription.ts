@@ -4,6 +4,7 @@ export async function transcribeAudio(
   client: OpenAI,
   audioBuffer: ArrayBuffer,
   model = 'whisper-1',
+  signal?: AbortSignal,
 ): Promise<{ value?: string; error?: string }> {
   try {
     const file = new File([audioBuffer], 'audio.webm', {
@@ -13,7 +14,7 @@ export async function transcribeAudio(
     const response = await client.audio.transcriptions.create({
       model,
       file,
-    });
+    }, { signal });
 
     return { value: response.text };
   } catch (err) {
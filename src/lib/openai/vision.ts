@@ -1,15 +1,17 @@
 import OpenAI from 'openai';
+import { applySystemPrefix } from './system-prompt';
 
 export async function analyzeImage(
   client: OpenAI,
   imageUrl: string,
   prompt: string,
   model = 'gpt-4o',
+  signal?: AbortSignal,
 ): Promise<{ value?: string; error?: string }> {
   try {
     const response = await client.chat.completions.create({
       model,
-      messages: [
+      messages: applySystemPrefix([
         {
           role: 'user',
           content: [
@@ -17,8 +19,8 @@ export async function analyzeImage(
             { type: 'image_url', image_url: { url: imageUrl } },
           ],
         },
-      ],
-    });
+      ]),
+    }, { signal });
 
     const content = response.choices[0]?.message?.content;
     return { value: content || '' };
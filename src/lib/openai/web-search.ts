@@ -1,4 +1,5 @@
 import OpenAI from 'openai';
+import { applySystemPrefix, getGlobalSystemPrefix } from './system-prompt';
 
 interface WebSearchResult {
   value?: string;
@@ -10,14 +11,17 @@ export async function searchWeb(
   client: OpenAI,
   query: string,
   model = 'gpt-4o-mini',
+  signal?: AbortSignal,
 ): Promise<WebSearchResult> {
   try {
     // Use the OpenAI Responses API with web_search tool
+    const systemPrefix = getGlobalSystemPrefix();
     const response = await (client as any).responses.create({
       model,
       tools: [{ type: 'web_search' as any }],
       input: query,
-    });
+      ...(systemPrefix && { instructions: systemPrefix }),
+    }, { signal });
 
     // Extract text output and sources
     let text = '';
@@ -48,15 +52,15 @@ export async function searchWeb(
     try {
       const response = await client.chat.completions.create({
         model,
-        messages: [
+        messages: applySystemPrefix([
           {
             role: 'system',
             content:
               'You are a web research assistant. Provide accurate, well-sourced information.',
           },
           { role: 'user', content: query },
-        ],
-      });
+        ]),
+      }, { signal });
 
       const content = response.choices[0]?.message?.content;
       return { value: content || '' };
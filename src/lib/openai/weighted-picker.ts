@@ -0,0 +1,35 @@
+interface Weighted {
+  weight: number;
+}
+
+/**
+ * Smooth weighted round-robin: over any window each target is picked in
+ * proportion to its weight, with picks interleaved rather than bunched.
+ * Disabled targets are skipped; `pick` returns undefined once all are.
+ */
+export function createWeightedPicker<T extends Weighted>(targets: T[]) {
+  const current = new Map<T, number>(targets.map((t) => [t, 0]));
+  const disabled = new Set<T>();
+
+  return {
+    pick(): T | undefined {
+      const active = targets.filter((t) => !disabled.has(t));
+      if (active.length === 0) return undefined;
+
+      const total = active.reduce((sum, t) => sum + t.weight, 0);
+      let best = active[0];
+      for (const t of active) {
+        current.set(t, current.get(t)! + t.weight);
+        if (current.get(t)! > current.get(best)!) best = t;
+      }
+      current.set(best, current.get(best)! - total);
+      return best;
+    },
+    disable(target: T) {
+      disabled.add(target);
+    },
+    allDisabled() {
+      return disabled.size === targets.length;
+    },
+  };
+}
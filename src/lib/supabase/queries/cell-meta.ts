@@ -24,7 +24,10 @@ export async function upsertCellMeta(
     generating?: boolean;
     validated?: boolean;
     error?: string | null;
+    error_status?: number | null;
     sources?: any;
+    prompt_used?: string | null;
+    prompt_hash?: string | null;
   },
 ) {
   const { error } = await supabase.from('column_cells').upsert(
@@ -34,7 +37,15 @@ export async function upsertCellMeta(
       generating: params.generating ?? false,
       validated: params.validated ?? false,
       error: params.error ?? null,
+      error_status: params.error_status ?? null,
       sources: params.sources ?? null,
+      // Left untouched unless given so status updates keep the stored prompt
+      ...(params.prompt_used !== undefined && {
+        prompt_used: params.prompt_used,
+      }),
+      ...(params.prompt_hash !== undefined && {
+        prompt_hash: params.prompt_hash,
+      }),
     },
     { onConflict: 'column_id,row_idx' },
   );
@@ -48,14 +59,87 @@ export async function getCellMetaBatch(
   offset: number,
   limit: number,
 ) {
-  const { data, error } = await supabase
+  // PostgREST caps responses at 1000 rows, so page through wide ranges
+  const PAGE_SIZE = 1000;
+  const rows: any[] = [];
+
+  for (let from = 0; ; from += PAGE_SIZE) {
+    const { data, error } = await supabase
+      .from('column_cells')
+      .select('*')
+      .eq('column_id', columnId)
+      .gte('row_idx', offset)
+      .lt('row_idx', offset + limit)
+      .order('row_idx', { ascending: true })
+      .range(from, from + PAGE_SIZE - 1);
+
+    if (error) throw error;
+    rows.push(...data);
+    if (data.length < PAGE_SIZE) break;
+  }
+
+  return rows;
+}
+
+/**
+ * Clears the generating flag left on cells in [from, to) by a run that never
+ * finished
+ */
+export async function clearStuckGenerating(
+  supabase: SupabaseClient,
+  columnId: string,
+  from: number,
+  to: number,
+) {
+  const { error } = await supabase
     .from('column_cells')
-    .select('*')
+    .update({ generating: false })
     .eq('column_id', columnId)
-    .gte('row_idx', offset)
-    .lt('row_idx', offset + limit)
-    .order('row_idx', { ascending: true });
+    .eq('generating', true)
+    .gte('row_idx', from)
+    .lt('row_idx', to);
 
   if (error) throw error;
-  return data;
+}
+
+/**
+ * Every errored cell in a dataset with its column name, read straight from
+ * the cell metadata rather than the full table
+ */
+export async function getDatasetErrors(
+  supabase: SupabaseClient,
+  datasetId: string,
+) {
+  const PAGE_SIZE = 1000;
+  const rows: Array<{
+    column: string;
+    row_idx: number;
+    error: string;
+    status: number | null;
+  }> = [];
+
+  for (let from = 0; ; from += PAGE_SIZE) {
+    const { data, error } = await supabase
+      .from('column_cells')
+      .select('row_idx, error, error_status, columns!inner(name, dataset_id)')
+      .eq('columns.dataset_id', datasetId)
+      .not('error', 'is', null)
+      .order('row_idx', { ascending: true })
+      // row_idx repeats across columns; a unique tiebreaker keeps pages stable
+      .order('id', { ascending: true })
+      .range(from, from + PAGE_SIZE - 1);
+
+    if (error) throw error;
+    rows.push(
+      ...data.map((cell: any) => ({
+        column: cell.columns.name,
+        row_idx: cell.row_idx,
+        error: cell.error,
+        status: cell.error_status,
+      })),
+    );
+    if (data.length < PAGE_SIZE) break;
+  }
+
+  return rows;
 }
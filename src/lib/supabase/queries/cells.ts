@@ -20,6 +20,30 @@ export async function getCellValues(
   return data;
 }
 
+export async function getColumnCellValues(
+  supabase: SupabaseClient,
+  columnId: string,
+) {
+  // PostgREST caps responses at 1000 rows, so page through the column
+  const PAGE_SIZE = 1000;
+  const rows: Array<{ row_idx: number; value: any }> = [];
+
+  for (let from = 0; ; from += PAGE_SIZE) {
+    const { data, error } = await supabase
+      .from('cell_values')
+      .select('row_idx, value')
+      .eq('column_id', columnId)
+      .order('row_idx', { ascending: true })
+      .range(from, from + PAGE_SIZE - 1);
+
+    if (error) throw error;
+    rows.push(...data);
+    if (data.length < PAGE_SIZE) break;
+  }
+
+  return rows;
+}
+
 export async function upsertCellValue(
   supabase: SupabaseClient,
   params: {
@@ -63,6 +87,24 @@ export async function bulkUpsertCellValues(
   }
 }
 
+/**
+ * Overwrites existing values of a column in a single transaction. Returns how
+ * many cells were updated.
+ */
+export async function replaceCellValues(
+  supabase: SupabaseClient,
+  columnId: string,
+  updates: Array<{ row_idx: number; value: any }>,
+) {
+  const { data, error } = await supabase.rpc('replace_cell_values', {
+    target_column_id: columnId,
+    updates,
+  });
+
+  if (error) throw error;
+  return data as number;
+}
+
 export async function deleteCellValues(
   supabase: SupabaseClient,
   datasetId: string,
@@ -92,6 +134,26 @@ export async function getRowCount(
   return count || 0;
 }
 
+/**
+ * One past the highest row index holding a value in any of the dataset's
+ * columns, i.e. the row count the table shows
+ */
+export async function getDatasetRowEnd(
+  supabase: SupabaseClient,
+  datasetId: string,
+) {
+  const { data, error } = await supabase
+    .from('cell_values')
+    .select('row_idx')
+    .eq('dataset_id', datasetId)
+    .order('row_idx', { ascending: false })
+    .limit(1)
+    .maybeSingle();
+
+  if (error) throw error;
+  return data ? data.row_idx + 1 : 0;
+}
+
 export async function getRowCells(
   supabase: SupabaseClient,
   rowIdx: number,
@@ -108,3 +170,45 @@ export async function getRowCells(
   if (error) throw error;
   return data;
 }
+
+/**
+ * Values of the given columns for one row, keyed by column name
+ */
+export async function getRowData(
+  supabase: SupabaseClient,
+  rowIdx: number,
+  columnIds: string[],
+): Promise<Record<string, any>> {
+  const rowCells = await getRowCells(supabase, rowIdx, columnIds);
+  return Object.fromEntries(
+    rowCells.map((cell: any) => [cell.columns?.name || cell.column_id, cell.value]),
+  );
+}
+
+export async function resetColumnCells(
+  supabase: SupabaseClient,
+  columnId: string,
+) {
+  const { data, error } = await supabase.rpc('reset_column_cells', {
+    target_column_id: columnId,
+  });
+
+  if (error) throw error;
+  return data as number;
+}
+
+export async function sampleRowIndices(
+  supabase: SupabaseClient,
+  datasetId: string,
+  size: number,
+  seed?: string,
+) {
+  const { data, error } = await supabase.rpc('sample_rows', {
+    target_dataset_id: datasetId,
+    sample_size: size,
+    sample_seed: seed ?? null,
+  });
+
+  if (error) throw error;
+  return data as number[];
+}
@@ -0,0 +1,52 @@
+import { SupabaseClient } from '@supabase/supabase-js';
+
+export async function snapshotColumn(
+  supabase: SupabaseClient,
+  columnId: string,
+  label?: string,
+) {
+  const { data, error } = await supabase.rpc('snapshot_column', {
+    target_column_id: columnId,
+    snapshot_label: label || null,
+  });
+
+  if (error) throw error;
+  return data as string;
+}
+
+export async function getColumnSnapshots(
+  supabase: SupabaseClient,
+  columnId: string,
+) {
+  const { data, error } = await supabase
+    .from('column_snapshots')
+    .select('*')
+    .eq('column_id', columnId)
+    .order('created_at', { ascending: false });
+
+  if (error) throw error;
+  return data;
+}
+
+export async function getSnapshotValues(
+  supabase: SupabaseClient,
+  snapshotId: string,
+) {
+  const PAGE_SIZE = 1000;
+  const rows: Array<{ row_idx: number; value: any }> = [];
+
+  for (let from = 0; ; from += PAGE_SIZE) {
+    const { data, error } = await supabase
+      .from('column_snapshot_values')
+      .select('row_idx, value')
+      .eq('snapshot_id', snapshotId)
+      .order('row_idx', { ascending: true })
+      .range(from, from + PAGE_SIZE - 1);
+
+    if (error) throw error;
+    rows.push(...data);
+    if (data.length < PAGE_SIZE) break;
+  }
+
+  return rows;
+}
@@ -1,5 +1,12 @@
 import { SupabaseClient } from '@supabase/supabase-js';
-import type { ColumnKind } from '@/lib/types/domain';
+import { findDependentColumns } from '@/lib/utils/prompt-template';
+import type {
+  ColumnCorrelation,
+  ColumnKind,
+  ColumnQuality,
+  ColumnValidation,
+  Provider,
+} from '@/lib/types/domain';
 
 export async function getDatasetColumns(
   supabase: SupabaseClient,
@@ -20,6 +27,17 @@ export async function getDatasetColumns(
   return data;
 }
 
+export async function getColumn(supabase: SupabaseClient, id: string) {
+  const { data, error } = await supabase
+    .from('columns')
+    .select('*')
+    .eq('id', id)
+    .single();
+
+  if (error) throw error;
+  return data;
+}
+
 export async function createColumn(
   supabase: SupabaseClient,
   params: {
@@ -54,6 +72,7 @@ export async function updateColumn(
     type: string;
     visible: boolean;
     position: number;
+    validation: ColumnValidation | null;
   }>,
 ) {
   const { error } = await supabase
@@ -63,11 +82,80 @@ export async function updateColumn(
   if (error) throw error;
 }
 
+/**
+ * Renames a column and, unless disabled, rewrites references to it in the
+ * dataset's other prompts. Returns how many prompts were rewritten.
+ */
+export async function renameColumn(
+  supabase: SupabaseClient,
+  id: string,
+  newName: string,
+  rewriteRefs = true,
+) {
+  const { data, error } = await supabase.rpc('rename_column', {
+    target_column_id: id,
+    new_name: newName,
+    rewrite_refs: rewriteRefs,
+  });
+
+  if (error) throw error;
+  return data as number;
+}
+
+export async function changeColumnKind(
+  supabase: SupabaseClient,
+  id: string,
+  kind: ColumnKind,
+  options: {
+    prompt?: string;
+    model?: string;
+    provider?: Provider | null;
+    referenceIds?: string[];
+    clearCells?: boolean;
+  } = {},
+) {
+  const { error } = await supabase.rpc('change_column_kind', {
+    target_column_id: id,
+    new_kind: kind,
+    new_prompt: options.prompt ?? null,
+    new_model: options.model ?? null,
+    new_provider: options.provider ?? null,
+    reference_ids: options.referenceIds ?? [],
+    clear_cells: options.clearCells ?? false,
+  });
+
+  if (error) throw error;
+}
+
 export async function deleteColumn(supabase: SupabaseClient, id: string) {
   const { error } = await supabase.from('columns').delete().eq('id', id);
   if (error) throw error;
 }
 
+/**
+ * Inserts a column before the one currently at `position`, shifting it and
+ * every later column right in one transaction. Returns the new column's id.
+ */
+export async function insertColumnAt(
+  supabase: SupabaseClient,
+  params: {
+    dataset_id: string;
+    name: string;
+    kind: ColumnKind;
+    position: number;
+  },
+) {
+  const { data, error } = await supabase.rpc('insert_column_at', {
+    target_dataset_id: params.dataset_id,
+    column_name: params.name,
+    column_kind: params.kind,
+    at_position: params.position,
+  });
+
+  if (error) throw error;
+  return data as string;
+}
+
 export async function getMaxPosition(
   supabase: SupabaseClient,
   datasetId: string,
@@ -81,3 +169,150 @@ export async function getMaxPosition(
 
   return data && data.length > 0 ? data[0].position : -1;
 }
+
+export async function duplicateColumn(
+  supabase: SupabaseClient,
+  columnId: string,
+  copyCells: boolean,
+  newName?: string,
+) {
+  const { data, error } = await supabase.rpc('duplicate_column', {
+    source_column_id: columnId,
+    copy_cells: copyCells,
+    new_name: newName || null,
+  });
+
+  if (error) throw error;
+  return data as string;
+}
+
+export async function splitColumn(
+  supabase: SupabaseClient,
+  columnId: string,
+  delimiter: string,
+  newColumnNames: string[],
+  overflow: 'drop' | 'merge' = 'drop',
+) {
+  const { data, error } = await supabase.rpc('split_column', {
+    source_column_id: columnId,
+    delimiter,
+    new_column_names: newColumnNames,
+    overflow,
+  });
+
+  if (error) throw error;
+  return data as string[];
+}
+
+export async function concatColumns(
+  supabase: SupabaseClient,
+  params: {
+    dataset_id: string;
+    source_column_ids: string[];
+    separator: string;
+    new_column_name: string;
+    skip_empty?: boolean;
+  },
+) {
+  const { data, error } = await supabase.rpc('concat_columns', {
+    target_dataset_id: params.dataset_id,
+    source_column_ids: params.source_column_ids,
+    separator: params.separator,
+    new_column_name: params.new_column_name,
+    skip_empty: params.skip_empty ?? true,
+  });
+
+  if (error) throw error;
+  return data as string;
+}
+
+export async function getColumnQuality(
+  supabase: SupabaseClient,
+  columnId: string,
+) {
+  const { data, error } = await supabase.rpc('column_quality', {
+    target_column_id: columnId,
+  });
+
+  if (error) throw error;
+  return (data as ColumnQuality[])[0];
+}
+
+export async function getColumnCorrelation(
+  supabase: SupabaseClient,
+  columnIds: string[],
+) {
+  const { data, error } = await supabase.rpc('column_correlation', {
+    target_column_ids: columnIds,
+  });
+
+  if (error) throw error;
+  return data as ColumnCorrelation[];
+}
+
+export async function getColumnDependents(
+  supabase: SupabaseClient,
+  columnId: string,
+) {
+  const column = await getColumn(supabase, columnId);
+  const columns = await getDatasetColumns(supabase, column.dataset_id);
+  const dependentIds = findDependentColumns(
+    column.name,
+    columns
+      .filter((c: any) => c.id !== columnId && c.kind === 'dynamic')
+      .map((c: any) => ({ id: c.id, prompt: c.processes?.[0]?.prompt })),
+  );
+
+  return columns
+    .filter((c: any) => dependentIds.includes(c.id))
+    .map((c: any) => ({ id: c.id as string, name: c.name as string }));
+}
+
+/**
+ * Groups of columns in a dataset that share a name, in position order. Such
+ * names make `{{Name}}` references ambiguous.
+ */
+export async function findDuplicateColumns(
+  supabase: SupabaseClient,
+  datasetId: string,
+) {
+  const columns = await getDatasetColumns(supabase, datasetId);
+  const byName = new Map<
+    string,
+    Array<{ id: string; name: string; position: number }>
+  >();
+  for (const c of columns as any[]) {
+    const group = byName.get(c.name) ?? [];
+    group.push({ id: c.id, name: c.name, position: c.position });
+    byName.set(c.name, group);
+  }
+
+  return Array.from(byName.values()).filter((group) => group.length > 1);
+}
+
+/**
+ * Keeps the first column of each duplicate group and suffixes the others as
+ * `Name (2)`, `Name (3)`, skipping names already taken. References are not
+ * rewritten, since they already resolve to the first column.
+ */
+export async function resolveDuplicateColumns(
+  supabase: SupabaseClient,
+  datasetId: string,
+) {
+  const columns = await getDatasetColumns(supabase, datasetId);
+  const taken = new Set<string>(columns.map((c: any) => c.name));
+  const renamed: Array<{ id: string; from: string; to: string }> = [];
+
+  for (const group of await findDuplicateColumns(supabase, datasetId)) {
+    let n = 2;
+    for (const column of group.slice(1)) {
+      while (taken.has(`${column.name} (${n})`)) n++;
+      const name = `${column.name} (${n})`;
+      await updateColumn(supabase, column.id, { name });
+      taken.add(name);
+      renamed.push({ id: column.id, from: column.name, to: name });
+    }
+  }
+
+  return renamed;
+}
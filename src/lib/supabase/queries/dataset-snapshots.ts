@@ -0,0 +1,41 @@
+import { SupabaseClient } from '@supabase/supabase-js';
+
+export async function snapshotDataset(
+  supabase: SupabaseClient,
+  datasetId: string,
+  label?: string,
+) {
+  const { data, error } = await supabase.rpc('snapshot_dataset', {
+    target_dataset_id: datasetId,
+    snapshot_label: label || null,
+  });
+
+  if (error) throw error;
+  return data as string;
+}
+
+export async function getDatasetSnapshots(
+  supabase: SupabaseClient,
+  datasetId: string,
+) {
+  const { data, error } = await supabase
+    .from('dataset_snapshots')
+    .select('*')
+    .eq('dataset_id', datasetId)
+    .order('created_at', { ascending: false });
+
+  if (error) throw error;
+  return data;
+}
+
+export async function restoreDatasetSnapshot(
+  supabase: SupabaseClient,
+  snapshotId: string,
+) {
+  const { data, error } = await supabase.rpc('restore_dataset_snapshot', {
+    target_snapshot_id: snapshotId,
+  });
+
+  if (error) throw error;
+  return data as string;
+}
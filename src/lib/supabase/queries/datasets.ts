@@ -1,4 +1,5 @@
 import { SupabaseClient } from '@supabase/supabase-js';
+import type { Provider } from '@/lib/types/domain';
 
 export async function getDatasets(supabase: SupabaseClient) {
   const { data, error } = await supabase
@@ -25,10 +26,15 @@ export async function createDataset(
   supabase: SupabaseClient,
   name: string,
   userId: string,
+  defaultProvider?: Provider | null,
 ) {
   const { data, error } = await supabase
     .from('datasets')
-    .insert({ name, user_id: userId })
+    .insert({
+      name,
+      user_id: userId,
+      default_provider: defaultProvider || null,
+    })
     .select()
     .single();
 
@@ -52,3 +58,73 @@ export async function renameDataset(
     .eq('id', id);
   if (error) throw error;
 }
+
+export async function setDatasetDefaultProvider(
+  supabase: SupabaseClient,
+  id: string,
+  provider: Provider | null,
+) {
+  const { error } = await supabase
+    .from('datasets')
+    .update({ default_provider: provider })
+    .eq('id', id);
+  if (error) throw error;
+}
+
+export async function updateDatasetNotes(
+  supabase: SupabaseClient,
+  id: string,
+  notes: string | null,
+) {
+  const { error } = await supabase
+    .from('datasets')
+    .update({ notes })
+    .eq('id', id);
+  if (error) throw error;
+}
+
+export async function searchDatasets(supabase: SupabaseClient, query: string) {
+  const pattern = `%${query.replace(/[\\%_]/g, (c) => `\\${c}`)}%`;
+
+  const [byName, byColumn] = await Promise.all([
+    supabase
+      .from('datasets')
+      .select('*')
+      .ilike('name', pattern)
+      .order('created_at', { ascending: false }),
+    supabase
+      .from('columns')
+      .select('name, datasets!inner(*)')
+      .ilike('name', pattern),
+  ]);
+
+  if (byName.error) throw byName.error;
+  if (byColumn.error) throw byColumn.error;
+
+  const results = new Map<
+    string,
+    { dataset: any; name_match: boolean; matching_columns: string[] }
+  >();
+
+  for (const dataset of byName.data) {
+    results.set(dataset.id, {
+      dataset,
+      name_match: true,
+      matching_columns: [],
+    });
+  }
+
+  for (const column of byColumn.data as any[]) {
+    const dataset = column.datasets;
+    if (!results.has(dataset.id)) {
+      results.set(dataset.id, {
+        dataset,
+        name_match: false,
+        matching_columns: [],
+      });
+    }
+    results.get(dataset.id)!.matching_columns.push(column.name);
+  }
+
+  return Array.from(results.values());
+}
@@ -37,3 +37,47 @@ export async function searchEmbeddings(
     similarity: number;
   }>;
 }
+
+/**
+ * Ids and sources of the embeddings whose source starts with `prefix`
+ */
+export async function getEmbeddingIdsBySourcePrefix(
+  supabase: SupabaseClient,
+  datasetId: string,
+  prefix: string,
+) {
+  // PostgREST caps responses at 1000 rows, so page through the matches
+  const PAGE_SIZE = 1000;
+  const rows: Array<{ id: string; source_uri: string }> = [];
+
+  for (let from = 0; ; from += PAGE_SIZE) {
+    const { data, error } = await supabase
+      .from('embeddings')
+      .select('id, source_uri')
+      .eq('dataset_id', datasetId)
+      .like('source_uri', `${prefix}%`)
+      .order('id', { ascending: true })
+      .range(from, from + PAGE_SIZE - 1);
+
+    if (error) throw error;
+    rows.push(...data);
+    if (data.length < PAGE_SIZE) break;
+  }
+
+  return rows;
+}
+
+export async function deleteEmbeddingsByIds(
+  supabase: SupabaseClient,
+  ids: string[],
+) {
+  // Keep the id list well under URL length limits
+  const CHUNK_SIZE = 100;
+  for (let i = 0; i < ids.length; i += CHUNK_SIZE) {
+    const { error } = await supabase
+      .from('embeddings')
+      .delete()
+      .in('id', ids.slice(i, i + CHUNK_SIZE));
+    if (error) throw error;
+  }
+}
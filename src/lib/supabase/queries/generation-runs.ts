@@ -0,0 +1,139 @@
+import { SupabaseClient } from '@supabase/supabase-js';
+
+export async function insertGenerationRun(
+  supabase: SupabaseClient,
+  run: {
+    dataset_id: string;
+    column_id: string;
+    provider: string;
+    model: string;
+    task: string;
+    total: number;
+    completed: number;
+    failed: number;
+    error?: string | null;
+    started_at: string;
+  },
+) {
+  const { error } = await supabase.from('generation_runs').insert(run);
+  if (error) throw error;
+}
+
+export async function getGenerationRuns(
+  supabase: SupabaseClient,
+  datasetId: string,
+  limit = 20,
+) {
+  const { data, error } = await supabase
+    .from('generation_runs')
+    .select('*, columns(name)')
+    .eq('dataset_id', datasetId)
+    .order('finished_at', { ascending: false })
+    .limit(limit);
+
+  if (error) throw error;
+  return data;
+}
+
+export async function startActiveRun(
+  supabase: SupabaseClient,
+  run: {
+    id: string;
+    dataset_id: string;
+    column_id: string;
+    request: Record<string, any>;
+    end_row: number;
+  },
+) {
+  const { error } = await supabase.from('active_runs').insert(run);
+  if (error) throw error;
+}
+
+// Options a resumed run needs to behave like the original one
+const RESUMABLE_FIELDS = [
+  'dataset_id',
+  'column_id',
+  'column_name',
+  'process',
+  'on_prompt_overflow',
+  'max_cell_bytes',
+  'on_oversized_cell',
+  'logprobs',
+  'top_logprobs',
+  'reasoning_effort',
+  'force_json',
+  'greedy',
+  'cell_timeout_secs',
+  'tools',
+  'only_stale',
+  'filters',
+];
+
+/**
+ * The part of a generate request that is stored to resume it. Only known
+ * options are kept, so credentials sent with a request are never written.
+ */
+export function toResumableRequest(body: object): Record<string, any> {
+  const request = body as Record<string, any>;
+  const resumable: Record<string, any> = Object.fromEntries(
+    RESUMABLE_FIELDS.filter((field) => request[field] !== undefined).map(
+      (field) => [field, request[field]],
+    ),
+  );
+  // Weighted providers are kept without their API keys; a resumed run uses
+  // the key it is posted with
+  if (Array.isArray(request.providers)) {
+    resumable.providers = request.providers.map((p: any) => ({
+      provider: p.provider,
+      weight: p.weight,
+      ...(p.model && { model: p.model }),
+    }));
+  }
+  return resumable;
+}
+
+export async function getActiveRun(supabase: SupabaseClient, id: string) {
+  const { data, error } = await supabase
+    .from('active_runs')
+    .select('*')
+    .eq('id', id)
+    .maybeSingle();
+
+  if (error) throw error;
+  return data;
+}
+
+export async function updateActiveRunProgress(
+  supabase: SupabaseClient,
+  id: string,
+  lastCompletedRow: number,
+) {
+  const { error } = await supabase
+    .from('active_runs')
+    .update({
+      last_completed_row: lastCompletedRow,
+      updated_at: new Date().toISOString(),
+    })
+    .eq('id', id);
+
+  if (error) throw error;
+}
+
+export async function deleteActiveRun(supabase: SupabaseClient, id: string) {
+  const { error } = await supabase.from('active_runs').delete().eq('id', id);
+  if (error) throw error;
+}
+
+export async function getActiveRuns(
+  supabase: SupabaseClient,
+  datasetId: string,
+) {
+  const { data, error } = await supabase
+    .from('active_runs')
+    .select('*, columns(name)')
+    .eq('dataset_id', datasetId)
+    .order('updated_at', { ascending: false });
+
+  if (error) throw error;
+  return data;
+}
@@ -1,5 +1,5 @@
 import { SupabaseClient } from '@supabase/supabase-js';
-import type { TaskType } from '@/lib/types/domain';
+import type { Provider, TaskType } from '@/lib/types/domain';
 
 export async function getProcess(
   supabase: SupabaseClient,
@@ -22,6 +22,7 @@ export async function upsertProcess(
     prompt: string;
     model: string;
     task: TaskType;
+    provider?: Provider | null;
     search_enabled: boolean;
     image_column_id?: string | null;
     columns_references?: string[];
@@ -36,6 +37,7 @@ export async function upsertProcess(
         prompt: params.prompt,
         model: params.model,
         task: params.task,
+        provider: params.provider || null,
         search_enabled: params.search_enabled,
         image_column_id: params.image_column_id || null,
       },
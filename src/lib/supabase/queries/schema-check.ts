@@ -0,0 +1,53 @@
+import { SupabaseClient } from '@supabase/supabase-js';
+
+// Latest migration the app code expects to have been applied
+export const EXPECTED_MIGRATION = '20260326000026_replace_cell_values_rpc';
+
+// Columns the app reads or writes, per table, including later migrations
+const EXPECTED_SCHEMA: Record<string, string[]> = {
+  datasets: ['id', 'name', 'user_id', 'default_provider', 'notes', 'created_at'],
+  columns: ['id', 'dataset_id', 'name', 'type', 'kind', 'visible', 'position', 'validation'],
+  column_cells: ['id', 'column_id', 'row_idx', 'error', 'validated', 'generating', 'sources', 'prompt_used', 'prompt_hash', 'error_status'],
+  cell_values: ['dataset_id', 'column_id', 'row_idx', 'value'],
+  processes: ['id', 'column_id', 'prompt', 'model', 'task', 'search_enabled', 'image_column_id', 'provider'],
+  process_columns: ['process_id', 'column_id'],
+  embeddings: ['id', 'dataset_id', 'source_uri', 'text'],
+  generation_runs: ['id', 'dataset_id', 'column_id'],
+  active_runs: ['id', 'dataset_id', 'column_id', 'request', 'end_row', 'last_completed_row'],
+  user_settings: ['user_id', 'default_provider', 'store_prompts'],
+  column_snapshots: ['id', 'column_id', 'label'],
+  dataset_snapshots: ['id', 'dataset_id', 'label', 'dataset_name'],
+};
+
+/**
+ * Probes every expected table and column with an empty select, so an out of
+ * date database is reported up front instead of as a confusing query error
+ * deep inside a route.
+ */
+export async function checkSchemaIntegrity(supabase: SupabaseClient) {
+  const problems: string[] = [];
+
+  for (const [table, columns] of Object.entries(EXPECTED_SCHEMA)) {
+    const { error } = await supabase.from(table).select('*').limit(0);
+    if (error) {
+      problems.push(`Table ${table}: ${error.message}`);
+      continue;
+    }
+
+    for (const column of columns) {
+      const { error: columnError } = await supabase
+        .from(table)
+        .select(column)
+        .limit(0);
+      if (columnError) {
+        problems.push(`Column ${table}.${column}: ${columnError.message}`);
+      }
+    }
+  }
+
+  return {
+    ok: problems.length === 0,
+    expected_migration: EXPECTED_MIGRATION,
+    problems,
+  };
+}
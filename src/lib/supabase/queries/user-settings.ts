@@ -0,0 +1,37 @@
+import { SupabaseClient } from '@supabase/supabase-js';
+import type { Provider } from '@/lib/types/domain';
+
+export async function getUserSettings(
+  supabase: SupabaseClient,
+  userId: string,
+) {
+  const { data, error } = await supabase
+    .from('user_settings')
+    .select('*')
+    .eq('user_id', userId)
+    .maybeSingle();
+
+  if (error) throw error;
+  return data;
+}
+
+export async function upsertUserSettings(
+  supabase: SupabaseClient,
+  userId: string,
+  settings: Partial<{
+    default_provider: Provider | null;
+    store_prompts: boolean;
+  }>,
+) {
+  const { data, error } = await supabase
+    .from('user_settings')
+    .upsert(
+      { user_id: userId, ...settings, updated_at: new Date().toISOString() },
+      { onConflict: 'user_id' },
+    )
+    .select()
+    .single();
+
+  if (error) throw error;
+  return data;
+}
@@ -12,11 +12,17 @@ export interface CellSource {
   snippet: string;
 }
 
+export interface ChatMessage {
+  role: 'system' | 'user' | 'assistant';
+  content: string;
+}
+
 export interface Process {
   id?: string;
   prompt: string;
   model: string;
   task: TaskType;
+  provider?: Provider;
   search_enabled: boolean;
   image_column_id?: string;
   columns_references?: string[];
@@ -34,10 +40,20 @@ export interface Cell {
   validated: boolean;
   value?: any;
   error?: string;
+  // HTTP status of the failed provider call, e.g. 429
+  error_status?: number | null;
   sources?: CellSource[];
+  prompt_used?: string | null;
+  prompt_hash?: string | null;
   column_id?: string;
 }
 
+export interface ColumnValidation {
+  required?: boolean;
+  regex?: string;
+  one_of?: string[];
+}
+
 export interface Column {
   id: string;
   dataset_id: string;
@@ -46,14 +62,34 @@ export interface Column {
   kind: ColumnKind;
   visible: boolean;
   position: number;
+  validation?: ColumnValidation;
   process?: Process;
   cells: Cell[];
 }
 
+export interface ColumnQuality {
+  row_count: number;
+  non_empty_pct: number;
+  error_pct: number;
+  avg_length: number;
+  distinct_count: number;
+}
+
+export interface ColumnCorrelation {
+  column_a: string;
+  column_b: string;
+  // Null when either column is constant over the shared rows
+  correlation: number | null;
+  sample_size: number;
+}
+
 export interface Dataset {
   id: string;
   name: string;
   user_id: string;
+  default_provider?: Provider | null;
+  // Markdown
+  notes?: string | null;
   created_at: string;
 }
 
@@ -64,12 +100,96 @@ export interface AutoDatasetConfig {
   text: string;
 }
 
+export interface GenerationProgress {
+  total: number;
+  completed: number;
+  failed: number;
+  // Per `provider:model` counts when a run spreads cells over several
+  providers?: Record<string, { completed: number; failed: number }>;
+}
+
+export interface DatasetGenerationProgress extends GenerationProgress {
+  columns: Record<string, GenerationProgress>;
+}
+
 export type Provider = 'openai' | 'mercury';
 
 export const MERCURY_BASE_URL = 'https://api.inceptionlabs.ai/v1';
 
+export interface ProviderField {
+  name: 'api_key' | 'model' | 'extra_headers';
+  label: string;
+  required: boolean;
+  placeholder?: string;
+}
+
+export interface ProviderDescriptor {
+  value: Provider;
+  label: string;
+  description: string;
+  // Fixed endpoint the provider is reached at; the SDK default when unset
+  base_url?: string;
+  fields: ProviderField[];
+  unsupported_tasks: TaskType[];
+}
+
+// Single source of what each provider needs, so forms can be built from it
+export const PROVIDER_DESCRIPTORS: ProviderDescriptor[] = [
+  {
+    value: 'openai',
+    label: 'OpenAI',
+    description: 'GPT-4o, GPT-4.1, o4-mini, and more',
+    fields: [
+      { name: 'api_key', label: 'API key', required: true, placeholder: 'sk-...' },
+      { name: 'model', label: 'Model', required: true },
+      { name: 'extra_headers', label: 'Custom headers', required: false },
+    ],
+    unsupported_tasks: [],
+  },
+  {
+    value: 'mercury',
+    label: 'Mercury (Inception Labs)',
+    description: 'Diffusion-based LLM — 5-10x faster, $0.25/M input tokens',
+    base_url: MERCURY_BASE_URL,
+    fields: [
+      { name: 'api_key', label: 'API key', required: true, placeholder: 'sk_...' },
+      { name: 'model', label: 'Model', required: true },
+      { name: 'extra_headers', label: 'Custom headers', required: false },
+    ],
+    // Text-only model
+    unsupported_tasks: [
+      'text-to-image',
+      'image-text-to-text',
+      'speech',
+      'transcription',
+    ],
+  },
+];
+
 export const DEFAULT_MODEL = 'gpt-4o-mini';
+// Cells a single generation run works on at once
 export const MAX_CONCURRENCY = 5;
+// Requests in flight per provider across all runs on a server instance
+export const PROVIDER_MAX_CONCURRENT_REQUESTS: Record<Provider, number> = {
+  openai: 10,
+  mercury: 10,
+};
 export const EXAMPLES_PROMPT_MAX_CONTEXT_SIZE = 8192;
 export const SOURCES_PROMPT_MAX_CONTEXT_SIZE = 61440;
 export const MAX_ROWS_IMPORT = 1000;
+// Texts per embeddings request, kept well under each provider's input cap
+export const EMBEDDING_BATCH_SIZES: Record<Provider, number> = {
+  openai: 256,
+  mercury: 64,
+};
+
+// Context windows (tokens) by model prefix; longest matching prefix wins
+export const MODEL_CONTEXT_WINDOWS: Record<string, number> = {
+  'gpt-4o': 128000,
+  'gpt-4.1': 1047576,
+  o4: 200000,
+  mercury: 128000,
+};
+export const DEFAULT_CONTEXT_WINDOW = 128000;
+export const MAX_CELL_BYTES = 1_000_000;
+export const OVERSIZED_CELL_MARKER = '… [truncated]';
@@ -0,0 +1,25 @@
+import { createHash } from 'crypto';
+import type { ChatMessage } from '@/lib/types/domain';
+
+/**
+ * Stable sha256 over what a generated cell depends on: the prompt template,
+ * the model and the referenced row values. Few-shot examples and search
+ * context vary run to run, so they are left out.
+ */
+export function hashCellInputs(
+  proc: { prompt: string; model: string; messages?: ChatMessage[] },
+  data: Record<string, any>,
+): string {
+  const sortedData = Object.fromEntries(
+    Object.entries(data).sort(([a], [b]) => a.localeCompare(b)),
+  );
+  return createHash('sha256')
+    .update(
+      JSON.stringify({
+        prompt: proc.messages ?? proc.prompt,
+        model: proc.model,
+        data: sortedData,
+      }),
+    )
+    .digest('hex');
+}
@@ -0,0 +1,15 @@
+// Same number syntax the column_correlation RPC accepts
+const NUMBER_PATTERN = /^\s*[-+]?(\d+\.?\d*|\.\d+)([eE][-+]?\d+)?\s*$/;
+
+/**
+ * Reads a cell value as a number. Returns null for empty cells and NaN for
+ * values that aren't numeric, so callers can tell the two apart.
+ */
+export function parseCellNumber(value: any): number | null {
+  if (value === null || value === undefined || value === '') return null;
+  if (typeof value === 'number') return Number.isFinite(value) ? value : NaN;
+  if (typeof value === 'string' && NUMBER_PATTERN.test(value)) {
+    return Number(value);
+  }
+  return NaN;
+}
@@ -0,0 +1,23 @@
+import { MAX_CELL_BYTES, OVERSIZED_CELL_MARKER } from '@/lib/types/domain';
+
+export function byteLength(value: string): number {
+  return new TextEncoder().encode(value).length;
+}
+
+export function isOversized(value: string, maxBytes = MAX_CELL_BYTES): boolean {
+  return byteLength(value) > maxBytes;
+}
+
+/**
+ * Cuts a string to at most `maxBytes` UTF-8 bytes (marker included) without
+ * splitting a multi-byte character.
+ */
+export function truncateToBytes(
+  value: string,
+  maxBytes = MAX_CELL_BYTES,
+): string {
+  const budget = maxBytes - byteLength(OVERSIZED_CELL_MARKER);
+  const bytes = new TextEncoder().encode(value).slice(0, Math.max(budget, 0));
+  const text = new TextDecoder().decode(bytes).replace(/�+$/, '');
+  return text + OVERSIZED_CELL_MARKER;
+}
@@ -0,0 +1,31 @@
+import type { ColumnValidation } from '@/lib/types/domain';
+
+/**
+ * Compiles the regex rule up front so an invalid pattern is reported once
+ * instead of failing every cell.
+ */
+export function compileValidation(validation: ColumnValidation) {
+  let regex: RegExp | undefined;
+  if (validation.regex) {
+    try {
+      regex = new RegExp(validation.regex);
+    } catch {
+      throw new Error(`Invalid validation regex: ${validation.regex}`);
+    }
+  }
+
+  return (value: any): string | null => {
+    const text = value == null ? '' : String(value);
+
+    if (!text.trim()) {
+      return validation.required ? 'Value is required' : null;
+    }
+    if (regex && !regex.test(text)) {
+      return `Value does not match ${validation.regex}`;
+    }
+    if (validation.one_of && !validation.one_of.includes(text)) {
+      return `Value must be one of: ${validation.one_of.join(', ')}`;
+    }
+    return null;
+  };
+}
@@ -0,0 +1,107 @@
+import { findMissingReferences } from '@/lib/utils/prompt-template';
+import { DEFAULT_MODEL, PROVIDER_DESCRIPTORS } from '@/lib/types/domain';
+import type { Provider, TaskType } from '@/lib/types/domain';
+
+const COLUMN_TYPES = ['text', 'image'];
+const TASKS: TaskType[] = [
+  'text-generation',
+  'text-to-image',
+  'image-text-to-text',
+  'speech',
+  'transcription',
+];
+const PROVIDERS = PROVIDER_DESCRIPTORS.map((p) => p.value);
+
+export interface SchemaColumn {
+  name: string;
+  type: string;
+  // Columns with a prompt are generated; the rest hold imported data
+  prompt?: string;
+  model: string;
+  task: TaskType;
+  provider: Provider | null;
+  search_enabled: boolean;
+}
+
+export interface DatasetSchema {
+  name?: string;
+  default_provider: Provider | null;
+  columns: SchemaColumn[];
+}
+
+/**
+ * Validates a checked-in dataset schema and fills in defaults. Every problem
+ * found is reported at once, so a schema can be fixed in one pass.
+ */
+export function parseDatasetSchema(input: unknown): DatasetSchema {
+  if (!input || typeof input !== 'object' || Array.isArray(input)) {
+    throw new Error('Schema must be a JSON object');
+  }
+  const raw = input as Record<string, any>;
+  if (!Array.isArray(raw.columns) || raw.columns.length === 0) {
+    throw new Error('Schema must list at least one column');
+  }
+
+  const problems: string[] = [];
+  if (raw.default_provider != null && !PROVIDERS.includes(raw.default_provider)) {
+    problems.push(`Unknown default_provider: ${raw.default_provider}`);
+  }
+
+  const names = raw.columns.map((c: any) => c?.name);
+  const columns: SchemaColumn[] = raw.columns.map((c: any, i: number) => {
+    const label = typeof c?.name === 'string' && c.name ? c.name : `#${i + 1}`;
+    if (typeof c?.name !== 'string' || !c.name.trim()) {
+      problems.push(`Column ${label} needs a name`);
+    } else if (names.indexOf(c.name) !== i) {
+      problems.push(`Column name ${c.name} is used more than once`);
+    }
+
+    const type = c?.type ?? 'text';
+    if (!COLUMN_TYPES.includes(type)) {
+      problems.push(`Column ${label} has unknown type ${type}`);
+    }
+    const task = c?.task ?? (type === 'image' ? 'text-to-image' : 'text-generation');
+    if (!TASKS.includes(task)) {
+      problems.push(`Column ${label} has unknown task ${task}`);
+    }
+    if (c?.provider != null && !PROVIDERS.includes(c.provider)) {
+      problems.push(`Column ${label} has unknown provider ${c.provider}`);
+    }
+
+    if (c?.prompt != null) {
+      if (typeof c.prompt !== 'string' || !c.prompt.trim()) {
+        problems.push(`Column ${label} has an empty prompt`);
+      } else {
+        const missing = findMissingReferences(
+          c.prompt,
+          names.filter((n: unknown) => n !== c.name),
+        );
+        if (missing.length > 0) {
+          problems.push(
+            `Column ${label} references unknown columns: ${missing.join(', ')}`,
+          );
+        }
+      }
+    }
+
+    return {
+      name: c?.name,
+      type,
+      prompt: c?.prompt ?? undefined,
+      model: c?.model ?? (type === 'image' ? 'gpt-image-1' : DEFAULT_MODEL),
+      task,
+      provider: c?.provider ?? null,
+      search_enabled: c?.search_enabled === true,
+    };
+  });
+
+  if (problems.length > 0) {
+    throw new Error(problems.join('; '));
+  }
+
+  return {
+    name: typeof raw.name === 'string' ? raw.name : undefined,
+    default_provider: raw.default_provider ?? null,
+    columns,
+  };
+}
@@ -0,0 +1,129 @@
+import { SupabaseClient } from '@supabase/supabase-js';
+import { createColumn, getMaxPosition } from '@/lib/supabase/queries/columns';
+import { bulkUpsertCellValues } from '@/lib/supabase/queries/cells';
+
+export interface ImportNormalization {
+  trim?: boolean;
+  // Unicode NFC, so visually identical text compares equal
+  nfc?: boolean;
+  // Runs of whitespace inside a value become a single space
+  collapse_whitespace?: boolean;
+  // Curly quotes and apostrophes become their ASCII forms
+  straighten_quotes?: boolean;
+}
+
+export const DEFAULT_NORMALIZATION: ImportNormalization = { trim: true };
+
+/**
+ * Parses the JSON-encoded normalization option of an import request. Missing
+ * means trimming only.
+ */
+export function parseNormalization(raw: string | null): ImportNormalization {
+  if (!raw) return DEFAULT_NORMALIZATION;
+
+  let parsed: unknown;
+  try {
+    parsed = JSON.parse(raw);
+  } catch {
+    throw new Error('normalize must be a JSON object');
+  }
+  if (!parsed || typeof parsed !== 'object' || Array.isArray(parsed)) {
+    throw new Error('normalize must be a JSON object');
+  }
+  return parsed as ImportNormalization;
+}
+
+/**
+ * Cleans up an imported string value. Non-string values pass through, and a
+ * value left empty stays an empty string.
+ */
+export function normalizeImportValue(
+  value: any,
+  options: ImportNormalization = DEFAULT_NORMALIZATION,
+) {
+  if (typeof value !== 'string') return value;
+
+  let text = value;
+  if (options.nfc) text = text.normalize('NFC');
+  if (options.straighten_quotes) {
+    text = text
+      .replace(/[\u2018\u2019\u201A\u201B]/g, "'")
+      .replace(/[\u201C\u201D\u201E\u201F]/g, '"');
+  }
+  if (options.collapse_whitespace) text = text.replace(/\s+/g, ' ');
+  if (options.trim) text = text.trim();
+  return text;
+}
+
+/**
+ * Resolves each header to a column id. Headers listed in `mapping` go to the
+ * existing column they map to; every other header gets a new static column.
+ */
+export async function createImportColumns(
+  supabase: SupabaseClient,
+  datasetId: string,
+  columnNames: string[],
+  mapping: Record<string, string> = {},
+) {
+  let maxPos = await getMaxPosition(supabase, datasetId);
+  const columnMap: Record<string, string> = {};
+  let created = 0;
+
+  for (const name of columnNames) {
+    if (mapping[name]) {
+      columnMap[name] = mapping[name];
+      continue;
+    }
+
+    maxPos++;
+    created++;
+    const col = await createColumn(supabase, {
+      dataset_id: datasetId,
+      name,
+      type: 'text',
+      kind: 'static',
+      position: maxPos,
+    });
+    columnMap[name] = col.id;
+  }
+
+  return { columnMap, created };
+}
+
+/**
+ * Writes parsed rows into a dataset, creating columns for unmapped headers
+ */
+export async function importRows(
+  supabase: SupabaseClient,
+  datasetId: string,
+  rows: Record<string, any>[],
+  mapping: Record<string, string> = {},
+  normalization: ImportNormalization = DEFAULT_NORMALIZATION,
+) {
+  // Get column names from first row
+  const columnNames = Object.keys(rows[0]);
+  const { columnMap, created } = await createImportColumns(
+    supabase,
+    datasetId,
+    columnNames,
+    mapping,
+  );
+
+  // Build cell values
+  const cellValues = rows.flatMap((row, rowIdx) =>
+    columnNames.map((colName) => ({
+      dataset_id: datasetId,
+      column_id: columnMap[colName],
+      row_idx: rowIdx,
+      value: normalizeImportValue(row[colName] ?? null, normalization),
+    })),
+  );
+
+  await bulkUpsertCellValues(supabase, cellValues);
+
+  return {
+    columns: columnNames.length,
+    created_columns: created,
+    rows: rows.length,
+  };
+}
@@ -0,0 +1,165 @@
+export interface ProgressReporter {
+  operationId: string;
+  send: (event: string, data?: Record<string, any>) => void;
+  // Aborted when the operation is cancelled; loops should stop at the next cell
+  signal: AbortSignal;
+}
+
+export interface OperationInfo {
+  id: string;
+  ownerId?: string;
+  startedAt: string;
+  lastEvent: string;
+}
+
+const HEARTBEAT_INTERVAL_MS = 5000;
+
+// In-flight operations on this server instance, keyed by correlation id
+const runningOperations = new Map<string, OperationInfo>();
+const abortControllers = new Map<string, AbortController>();
+
+export function getRunningOperation(id: string): OperationInfo | undefined {
+  return runningOperations.get(id);
+}
+
+/**
+ * Cancels every running operation owned by the user. Work already written
+ * stays; each operation stops at its next cell.
+ */
+export function abortAllOperations(ownerId: string): OperationInfo[] {
+  const aborted: OperationInfo[] = [];
+  for (const operation of runningOperations.values()) {
+    if (operation.ownerId !== ownerId) continue;
+    abortControllers.get(operation.id)?.abort();
+    aborted.push({ ...operation });
+  }
+  return aborted;
+}
+
+/**
+ * Cancels a single running operation owned by the user. Returns the
+ * operation, or undefined when it isn't running on this server.
+ */
+export function abortOperation(
+  id: string,
+  ownerId: string,
+): OperationInfo | undefined {
+  const operation = runningOperations.get(id);
+  if (!operation || operation.ownerId !== ownerId) return undefined;
+  abortControllers.get(id)?.abort();
+  return { ...operation };
+}
+
+/**
+ * Registers an operation that streams its own response, such as an export,
+ * so it can be listed and cancelled like a progress stream. Call `finish`
+ * once it ends.
+ */
+export function trackOperation(ownerId?: string) {
+  const operation: OperationInfo = {
+    id: crypto.randomUUID(),
+    ownerId,
+    startedAt: new Date().toISOString(),
+    lastEvent: 'started',
+  };
+  const abortController = new AbortController();
+  runningOperations.set(operation.id, operation);
+  abortControllers.set(operation.id, abortController);
+
+  return {
+    operation,
+    signal: abortController.signal,
+    finish: () => {
+      runningOperations.delete(operation.id);
+      abortControllers.delete(operation.id);
+    },
+  };
+}
+
+/**
+ * Runs a long operation and streams its named progress events to the client
+ * as SSE. Every event carries the operation's correlation id, and a periodic
+ * heartbeat lets a reconnecting client tell the operation is still alive.
+ */
+export function createProgressStream(
+  run: (reporter: ProgressReporter) => Promise<void>,
+  ownerId?: string,
+): Response {
+  const encoder = new TextEncoder();
+  const { operation, signal, finish } = trackOperation(ownerId);
+  const operationId = operation.id;
+
+  const stream = new ReadableStream({
+    async start(controller) {
+      let closed = false;
+      const send = (event: string, data: Record<string, any> = {}) => {
+        if (closed) return;
+        if (event !== 'heartbeat') operation.lastEvent = event;
+        try {
+          controller.enqueue(
+            encoder.encode(
+              `data: ${JSON.stringify({ event, operation_id: operationId, ...data })}\n\n`,
+            ),
+          );
+        } catch {
+          // Client disconnected; keep running so the work still completes
+          closed = true;
+        }
+      };
+
+      const heartbeat = setInterval(
+        () => send('heartbeat', { status: operation.lastEvent }),
+        HEARTBEAT_INTERVAL_MS,
+      );
+
+      try {
+        await run({ operationId, send, signal });
+      } finally {
+        clearInterval(heartbeat);
+        finish();
+        if (!closed) controller.close();
+      }
+    },
+  });
+
+  return new Response(stream, {
+    headers: {
+      'Content-Type': 'text/event-stream',
+      'Cache-Control': 'no-cache',
+      Connection: 'keep-alive',
+      'X-Operation-Id': operationId,
+    },
+  });
+}
+
+/**
+ * Consumes a progress stream produced by `createProgressStream`, calling
+ * `onEvent` for each event as it arrives. Events split across chunks are
+ * reassembled before parsing.
+ */
+export async function readProgressEvents(
+  body: ReadableStream<Uint8Array>,
+  onEvent: (data: Record<string, any>) => void,
+) {
+  const reader = body.getReader();
+  const decoder = new TextDecoder();
+  let buffer = '';
+
+  while (true) {
+    const { done, value } = await reader.read();
+    if (done) break;
+
+    buffer += decoder.decode(value, { stream: true });
+    const messages = buffer.split('\n\n');
+    buffer = messages.pop() || '';
+
+    for (const message of messages) {
+      if (!message.startsWith('data: ')) continue;
+      try {
+        onEvent(JSON.parse(message.slice(6)));
+      } catch {
+        // Skip malformed SSE messages
+      }
+    }
+  }
+}
@@ -0,0 +1,253 @@
+import { SupabaseClient } from '@supabase/supabase-js';
+import { APP_VERSION } from '@/lib/version';
+import {
+  createDataset,
+  deleteDataset,
+  getDatasets,
+  updateDatasetNotes,
+} from '@/lib/supabase/queries/datasets';
+import {
+  createColumn,
+  getDatasetColumns,
+  updateColumn,
+} from '@/lib/supabase/queries/columns';
+import { getProcess, upsertProcess } from '@/lib/supabase/queries/processes';
+import {
+  bulkUpsertCellValues,
+  getColumnCellValues,
+} from '@/lib/supabase/queries/cells';
+import type {
+  ColumnKind,
+  ColumnValidation,
+  Provider,
+  TaskType,
+} from '@/lib/types/domain';
+
+export const ARCHIVE_FORMAT_VERSION = 1;
+
+interface ArchiveColumn {
+  id: string;
+  name: string;
+  type: string;
+  kind: ColumnKind;
+  visible: boolean;
+  position: number;
+  validation: ColumnValidation | null;
+  process: {
+    prompt: string;
+    model: string;
+    task: TaskType;
+    provider: Provider | null;
+    search_enabled: boolean;
+    image_column_id: string | null;
+    columns_references: string[];
+  } | null;
+}
+
+interface ArchiveDataset {
+  name: string;
+  default_provider: Provider | null;
+  notes?: string | null;
+  columns: ArchiveColumn[];
+  cells: Array<{ column_id: string; row_idx: number; value: any }>;
+}
+
+export interface ProjectArchive {
+  manifest: {
+    app: 'braincells';
+    app_version: string;
+    format_version: number;
+    exported_at: string;
+    datasets: number;
+  };
+  datasets: ArchiveDataset[];
+}
+
+/**
+ * Collects every dataset the user owns, with its columns, prompts and cell
+ * values, into one version-stamped archive. Ids are kept only to link
+ * columns, references and cells within the archive.
+ */
+export async function buildProjectArchive(
+  supabase: SupabaseClient,
+): Promise<ProjectArchive> {
+  const datasets: ArchiveDataset[] = [];
+
+  for (const dataset of await getDatasets(supabase)) {
+    const columns: ArchiveColumn[] = [];
+    const cells: ArchiveDataset['cells'] = [];
+
+    for (const col of await getDatasetColumns(supabase, dataset.id)) {
+      const process = col.processes?.[0]
+        ? await getProcess(supabase, col.id)
+        : null;
+      columns.push({
+        id: col.id,
+        name: col.name,
+        type: col.type,
+        kind: col.kind,
+        visible: col.visible,
+        position: col.position,
+        validation: col.validation ?? null,
+        process: process && {
+          prompt: process.prompt,
+          model: process.model,
+          task: process.task,
+          provider: process.provider ?? null,
+          search_enabled: process.search_enabled,
+          image_column_id: process.image_column_id ?? null,
+          columns_references: process.process_columns.map(
+            (pc: { column_id: string }) => pc.column_id,
+          ),
+        },
+      });
+
+      for (const cell of await getColumnCellValues(supabase, col.id)) {
+        cells.push({ column_id: col.id, ...cell });
+      }
+    }
+
+    datasets.push({
+      name: dataset.name,
+      default_provider: dataset.default_provider ?? null,
+      notes: dataset.notes ?? null,
+      columns,
+      cells,
+    });
+  }
+
+  return {
+    manifest: {
+      app: 'braincells',
+      app_version: APP_VERSION,
+      format_version: ARCHIVE_FORMAT_VERSION,
+      exported_at: new Date().toISOString(),
+      datasets: datasets.length,
+    },
+    datasets,
+  };
+}
+
+/**
+ * Throws unless `archive` has the shape restoreProjectArchive relies on.
+ * Every dataset is checked up front so a malformed one late in the archive
+ * can't fail the restore after earlier datasets were already created.
+ */
+export function validateProjectArchive(
+  archive: any,
+): asserts archive is ProjectArchive {
+  if (archive?.manifest?.app !== 'braincells' || !Array.isArray(archive.datasets)) {
+    throw new Error('Not a BrainCells project archive');
+  }
+  if (archive.manifest.format_version > ARCHIVE_FORMAT_VERSION) {
+    throw new Error(
+      `Archive format ${archive.manifest.format_version} is newer than this app supports`,
+    );
+  }
+
+  archive.datasets.forEach((source: any, index: number) => {
+    const fail = (reason: string) => {
+      throw new Error(`Dataset ${index + 1} in the archive ${reason}`);
+    };
+    if (typeof source?.name !== 'string') fail('has no name');
+    if (!Array.isArray(source.columns)) fail('has no columns list');
+    if (!Array.isArray(source.cells)) fail('has no cells list');
+    for (const col of source.columns) {
+      if (typeof col?.id !== 'string' || typeof col.name !== 'string') {
+        fail('has a column without an id or name');
+      }
+      if (col.process && !Array.isArray(col.process.columns_references)) {
+        fail(`has a prompt on "${col.name}" without columns_references`);
+      }
+    }
+    for (const cell of source.cells) {
+      if (typeof cell?.column_id !== 'string' || !Number.isInteger(cell.row_idx)) {
+        fail('has a cell without a column_id or row_idx');
+      }
+    }
+  });
+}
+
+/**
+ * Recreates every dataset in an archive as a new dataset, so restoring never
+ * collides with or overwrites existing data. If any step fails, the datasets
+ * created so far are deleted again. Returns the new dataset ids.
+ */
+export async function restoreProjectArchive(
+  supabase: SupabaseClient,
+  userId: string,
+  archive: ProjectArchive,
+): Promise<string[]> {
+  validateProjectArchive(archive);
+
+  const created: string[] = [];
+  try {
+    for (const source of archive.datasets) {
+      const dataset = await createDataset(
+        supabase,
+        source.name,
+        userId,
+        source.default_provider,
+      );
+      created.push(dataset.id);
+      if (source.notes) {
+        await updateDatasetNotes(supabase, dataset.id, source.notes);
+      }
+
+      // Columns first, so processes can point at the new ids
+      const idMap = new Map<string, string>();
+      for (const col of source.columns) {
+        const newCol = await createColumn(supabase, {
+          dataset_id: dataset.id,
+          name: col.name,
+          type: col.type,
+          kind: col.kind,
+          position: col.position,
+        });
+        await updateColumn(supabase, newCol.id, {
+          visible: col.visible,
+          validation: col.validation,
+        });
+        idMap.set(col.id, newCol.id);
+      }
+
+      for (const col of source.columns) {
+        if (!col.process) continue;
+        await upsertProcess(supabase, {
+          column_id: idMap.get(col.id)!,
+          prompt: col.process.prompt,
+          model: col.process.model,
+          task: col.process.task,
+          provider: col.process.provider,
+          search_enabled: col.process.search_enabled,
+          image_column_id: col.process.image_column_id
+            ? idMap.get(col.process.image_column_id) ?? null
+            : null,
+          columns_references: col.process.columns_references
+            .map((id) => idMap.get(id))
+            .filter((id): id is string => !!id),
+        });
+      }
+
+      await bulkUpsertCellValues(
+        supabase,
+        source.cells
+          .filter((cell) => idMap.has(cell.column_id))
+          .map((cell) => ({
+            dataset_id: dataset.id,
+            column_id: idMap.get(cell.column_id)!,
+            row_idx: cell.row_idx,
+            value: cell.value,
+          })),
+      );
+    }
+  } catch (err) {
+    // Columns, processes and cells cascade with their dataset
+    for (const id of created) {
+      await deleteDataset(supabase, id).catch(() => {});
+    }
+    throw err;
+  }
+
+  return created;
+}
@@ -3,7 +3,7 @@ import {
   EXAMPLES_PROMPT_MAX_CONTEXT_SIZE,
   SOURCES_PROMPT_MAX_CONTEXT_SIZE,
 } from '@/lib/types/domain';
-import type { TaskType } from '@/lib/types/domain';
+import type { ChatMessage, TaskType } from '@/lib/types/domain';
 
 export interface Example {
   output: string;
@@ -155,11 +155,72 @@ You are a rigorous, intelligent data-processing engine. Generate only the reques
   );
 }
 
+// Value transforms usable as `{{fn:Column}}` or `{{fn:Column:arg}}`
+const TEMPLATE_FUNCTIONS: Record<
+  string,
+  { hasArg: boolean; apply: (value: string, arg?: number) => string }
+> = {
+  upper: { hasArg: false, apply: (value) => value.toUpperCase() },
+  lower: { hasArg: false, apply: (value) => value.toLowerCase() },
+  truncate: {
+    hasArg: true,
+    apply: (value, length) => value.slice(0, length),
+  },
+};
+
+/**
+ * Splits a `fn:Column[:arg]` tag into its parts. Returns null when the tag
+ * has no function prefix, so plain references are left alone.
+ */
+function parseFunctionTag(
+  tag: string,
+): { fn: string; name: string; arg?: number } | null {
+  const parts = tag.split(':');
+  if (parts.length < 2 || !/^[a-z]+$/.test(parts[0])) return null;
+
+  const [fn, ...rest] = parts;
+  if (TEMPLATE_FUNCTIONS[fn]?.hasArg && rest.length > 1) {
+    const arg = Number(rest[rest.length - 1]);
+    if (Number.isInteger(arg) && arg >= 0) {
+      return { fn, name: rest.slice(0, -1).join(':').trim(), arg };
+    }
+  }
+  return { fn, name: rest.join(':').trim() };
+}
+
 export const renderInstruction = (
   instruction: string,
   data: Record<string, any>,
 ): string => {
-  return mustache.render(instruction, data, undefined, {
+  // Function tags render from generated keys so transformed values are never
+  // re-parsed as template syntax
+  const view: Record<string, any> = { ...data };
+  let fnCount = 0;
+  const template = instruction.replace(/\{\{([^}]+)\}\}/g, (tag, body) => {
+    const name = body.trim();
+    // Column names that merely contain a colon still render as-is
+    if (/^[#/^!>&]/.test(name) || name in data) return tag;
+    const parsed = parseFunctionTag(name);
+    if (!parsed) return tag;
+
+    const fn = TEMPLATE_FUNCTIONS[parsed.fn];
+    if (!fn) {
+      throw new Error(`Unknown template function: ${parsed.fn}`);
+    }
+    if (fn.hasArg && parsed.arg === undefined) {
+      throw new Error(`Template function ${parsed.fn} needs a numeric argument`);
+    }
+
+    const value = data[parsed.name];
+    const key = `__fn_${fnCount++}`;
+    view[key] =
+      value === undefined || value === null
+        ? ''
+        : fn.apply(String(escapeValues(value)), parsed.arg);
+    return `{{${key}}}`;
+  });
+
+  return mustache.render(template, view, undefined, {
     escape: escapeValues,
   });
 };
@@ -219,22 +280,109 @@ Use the web sources below to accurately follow the user instruction. If the info
 };
 
 /**
- * Extracts column references from a prompt using the double-brace syntax
+ * Extracts every double-brace reference from a prompt, skipping Mustache
+ * section/comment/partial tags
  */
-export function extractColumnReferences(
-  prompt: string,
-  availableColumnNames: string[],
-): string[] {
+export function extractAllReferences(prompt: string): string[] {
   const references: string[] = [];
   const regex = /\{\{([^}]+)\}\}/g;
   let match: RegExpExecArray | null;
 
   while ((match = regex.exec(prompt)) !== null) {
-    const columnName = match[1].trim();
-    if (availableColumnNames.includes(columnName)) {
-      references.push(columnName);
+    const name = match[1].trim();
+    if (!/^[#/^!>&]/.test(name)) {
+      // `{{upper:Name}}` references Name; unknown functions stay as written
+      const parsed = parseFunctionTag(name);
+      references.push(
+        parsed && TEMPLATE_FUNCTIONS[parsed.fn] ? parsed.name : name,
+      );
     }
   }
 
   return references;
 }
+
+/**
+ * Extracts column references from a prompt using the double-brace syntax
+ */
+export function extractColumnReferences(
+  prompt: string,
+  availableColumnNames: string[],
+): string[] {
+  return extractAllReferences(prompt).filter((name) =>
+    availableColumnNames.includes(name),
+  );
+}
+
+/**
+ * Returns the ids of columns whose prompt references the given column name
+ */
+export function findDependentColumns(
+  columnName: string,
+  columns: Array<{ id: string; prompt?: string | null }>,
+): string[] {
+  return columns
+    .filter(
+      (c) => c.prompt && extractAllReferences(c.prompt).includes(columnName),
+    )
+    .map((c) => c.id);
+}
+
+/**
+ * Returns the references in a prompt that don't match any column name
+ */
+export function findMissingReferences(
+  prompt: string,
+  availableColumnNames: string[],
+): string[] {
+  return Array.from(
+    new Set(
+      extractAllReferences(prompt).filter(
+        (name) => !availableColumnNames.includes(name),
+      ),
+    ),
+  );
+}
+
+const CHAT_ROLES = ['system', 'user', 'assistant'];
+
+/**
+ * Parses a prompt written as a JSON array of `{ role, content }` messages.
+ * Returns null for plain-text prompts.
+ */
+export function parseMessagesPrompt(prompt: string): ChatMessage[] | null {
+  const trimmed = prompt.trim();
+  if (!trimmed.startsWith('[')) return null;
+
+  let parsed: unknown;
+  try {
+    parsed = JSON.parse(trimmed);
+  } catch {
+    return null;
+  }
+
+  if (
+    !Array.isArray(parsed) ||
+    parsed.length === 0 ||
+    !parsed.every(
+      (m) =>
+        m &&
+        CHAT_ROLES.includes(m.role) &&
+        typeof m.content === 'string',
+    )
+  ) {
+    return null;
+  }
+
+  return parsed as ChatMessage[];
+}
+
+export function renderMessages(
+  messages: ChatMessage[],
+  data: Record<string, any>,
+): ChatMessage[] {
+  return messages.map((message) => ({
+    role: message.role,
+    content: renderInstruction(message.content, data),
+  }));
+}
@@ -0,0 +1,38 @@
+import { createHmac } from 'crypto';
+
+export type RedactMode = 'hash' | 'mask' | 'drop';
+
+export const REDACT_MODES: RedactMode[] = ['hash', 'mask', 'drop'];
+const MASK = '****';
+
+/**
+ * Returns a function that redacts the named columns of an exported row.
+ * `hash` replaces values with a salted HMAC, so the same value always maps to
+ * the same token and joins across exports still work; `mask` blanks them;
+ * `drop` removes the column.
+ */
+export function createRowRedactor(
+  columnNames: string[],
+  mode: RedactMode,
+  salt: string,
+) {
+  const redactValue = (value: any) => {
+    if (value === null || value === undefined || value === '') return value;
+    if (mode === 'mask') return MASK;
+    const text = typeof value === 'string' ? value : JSON.stringify(value);
+    return createHmac('sha256', salt).update(text).digest('hex').slice(0, 16);
+  };
+
+  return (row: Record<string, any>) => {
+    const redacted = { ...row };
+    for (const name of columnNames) {
+      if (!(name in redacted)) continue;
+      if (mode === 'drop') {
+        delete redacted[name];
+      } else {
+        redacted[name] = redactValue(redacted[name]);
+      }
+    }
+    return redacted;
+  };
+}
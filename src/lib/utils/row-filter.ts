@@ -0,0 +1,76 @@
+import { SupabaseClient } from '@supabase/supabase-js';
+import { getCellValues } from '@/lib/supabase/queries/cells';
+
+export type RowFilterOp = 'eq' | 'neq' | 'contains' | 'empty' | 'not_empty';
+
+export const ROW_FILTER_OPS: RowFilterOp[] = [
+  'eq',
+  'neq',
+  'contains',
+  'empty',
+  'not_empty',
+];
+
+export interface RowFilter {
+  column_id: string;
+  op: RowFilterOp;
+  // Compared as text; unused by empty/not_empty
+  value?: string;
+}
+
+// PostgREST caps responses at 1000 rows
+const PAGE_SIZE = 1000;
+
+const cellText = (value: any) =>
+  value === null || value === undefined
+    ? ''
+    : typeof value === 'string'
+      ? value
+      : JSON.stringify(value);
+
+function matches(filter: RowFilter, value: any): boolean {
+  const text = cellText(value);
+  switch (filter.op) {
+    case 'eq':
+      return text === (filter.value ?? '');
+    case 'neq':
+      return text !== (filter.value ?? '');
+    case 'contains':
+      return text.toLowerCase().includes((filter.value ?? '').toLowerCase());
+    case 'empty':
+      return text === '';
+    case 'not_empty':
+      return text !== '';
+  }
+}
+
+/**
+ * Rows in [offset, end) that satisfy every filter. A row with no cell in a
+ * filtered column is treated as empty there.
+ */
+export async function findMatchingRows(
+  supabase: SupabaseClient,
+  datasetId: string,
+  filters: RowFilter[],
+  offset: number,
+  end: number,
+): Promise<number[]> {
+  let matching = Array.from({ length: end - offset }, (_, i) => offset + i);
+
+  for (const filter of filters) {
+    const values = new Map<number, any>();
+    for (let from = offset; from < end; from += PAGE_SIZE) {
+      const cells = await getCellValues(
+        supabase,
+        datasetId,
+        [filter.column_id],
+        from,
+        Math.min(PAGE_SIZE, end - from),
+      );
+      for (const cell of cells) values.set(cell.row_idx, cell.value);
+    }
+    matching = matching.filter((rowIdx) => matches(filter, values.get(rowIdx)));
+  }
+
+  return matching;
+}
@@ -0,0 +1,68 @@
+type SqliteType = 'INTEGER' | 'REAL' | 'TEXT';
+
+/**
+ * Turns a column name into a unique SQLite identifier made of letters,
+ * digits and underscores.
+ */
+function sanitizeIdentifiers(names: string[]): string[] {
+  const used = new Set<string>();
+  return names.map((name) => {
+    let base = name.trim().replace(/[^A-Za-z0-9_]+/g, '_').replace(/^_+|_+$/g, '');
+    if (!base) base = 'column';
+    if (/^\d/.test(base)) base = `c_${base}`;
+
+    let id = base;
+    for (let n = 2; used.has(id.toLowerCase()); n++) id = `${base}_${n}`;
+    used.add(id.toLowerCase());
+    return id;
+  });
+}
+
+function inferType(values: any[]): SqliteType {
+  const present = values.filter((v) => v !== null && v !== undefined);
+  if (present.length === 0) return 'TEXT';
+  if (present.every((v) => typeof v === 'boolean' || Number.isInteger(v))) {
+    return 'INTEGER';
+  }
+  if (present.every((v) => typeof v === 'number' && Number.isFinite(v))) {
+    return 'REAL';
+  }
+  return 'TEXT';
+}
+
+function sqlLiteral(value: any, type: SqliteType): string {
+  if (value === null || value === undefined) return 'NULL';
+  if (type === 'INTEGER' && typeof value === 'boolean') return value ? '1' : '0';
+  if (type !== 'TEXT') return String(value);
+  const text = typeof value === 'string' ? value : JSON.stringify(value);
+  return `'${text.replace(/'/g, "''")}'`;
+}
+
+/**
+ * Builds a SQLite script that creates one table with a column per dataset
+ * column and inserts every row. Load it with `sqlite3 data.db < export.sql`.
+ */
+export function toSqliteDump(
+  tableName: string,
+  columnNames: string[],
+  rows: Record<string, any>[],
+): string {
+  const [table] = sanitizeIdentifiers([tableName]);
+  const ids = sanitizeIdentifiers(columnNames);
+  const types = columnNames.map((name) => inferType(rows.map((r) => r[name])));
+
+  const lines = [
+    'BEGIN TRANSACTION;',
+    `CREATE TABLE "${table}" (${ids
+      .map((id, i) => `"${id}" ${types[i]}`)
+      .join(', ')});`,
+  ];
+
+  for (const row of rows) {
+    const values = columnNames.map((name, i) => sqlLiteral(row[name], types[i]));
+    lines.push(`INSERT INTO "${table}" VALUES (${values.join(', ')});`);
+  }
+
+  lines.push('COMMIT;');
+  return lines.join('\n') + '\n';
+}
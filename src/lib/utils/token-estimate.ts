@@ -0,0 +1,48 @@
+import {
+  DEFAULT_CONTEXT_WINDOW,
+  MODEL_CONTEXT_WINDOWS,
+} from '@/lib/types/domain';
+
+// Rough heuristic: ~4 characters per token for English text
+export const CHARS_PER_TOKEN = 4;
+
+export function estimateTokens(text: string): number {
+  return Math.ceil(text.length / CHARS_PER_TOKEN);
+}
+
+export function getContextWindow(model: string): number {
+  const match = Object.keys(MODEL_CONTEXT_WINDOWS)
+    .filter((prefix) => model.startsWith(prefix))
+    .sort((a, b) => b.length - a.length)[0];
+  return match ? MODEL_CONTEXT_WINDOWS[match] : DEFAULT_CONTEXT_WINDOW;
+}
+
+export function exceedsContextWindow(prompt: string, model: string): boolean {
+  return estimateTokens(prompt) > getContextWindow(model);
+}
+
+/**
+ * Shortens the longest string values in a row until the total fits within
+ * the given character budget. Non-string values are left untouched.
+ */
+export function truncateDataToFit(
+  data: Record<string, any>,
+  overflowChars: number,
+): Record<string, any> {
+  const result = { ...data };
+  let remaining = overflowChars;
+
+  const keys = Object.keys(result)
+    .filter((key) => typeof result[key] === 'string')
+    .sort((a, b) => result[b].length - result[a].length);
+
+  for (const key of keys) {
+    if (remaining <= 0) break;
+    const value: string = result[key];
+    const cut = Math.min(value.length, remaining);
+    result[key] = value.slice(0, value.length - cut);
+    remaining -= cut;
+  }
+
+  return result;
+}
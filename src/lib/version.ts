@@ -0,0 +1,4 @@
+import { version } from '../../package.json';
+
+// The one place the manifest is read from, since it sits outside `@/`
+export const APP_VERSION = version;
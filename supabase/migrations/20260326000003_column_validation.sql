@@ -0,0 +1,2 @@
+-- Optional validation rules for static (input) columns
+alter table columns add column validation jsonb;
@@ -0,0 +1,21 @@
+-- RPC function to reset a column's cells to pending while keeping the rows
+create or replace function reset_column_cells(target_column_id uuid)
+returns integer
+language plpgsql
+as $$
+declare
+  reset_count integer;
+begin
+  update cell_values
+  set value = null
+  where column_id = target_column_id;
+
+  get diagnostics reset_count = row_count;
+
+  update column_cells
+  set error = null, generating = false, validated = false, sources = null
+  where column_id = target_column_id;
+
+  return reset_count;
+end;
+$$;
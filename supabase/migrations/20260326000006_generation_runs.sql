@@ -0,0 +1,23 @@
+-- Generation runs (audit trail of column generations)
+create table generation_runs (
+  id uuid primary key default gen_random_uuid(),
+  dataset_id uuid not null references datasets(id) on delete cascade,
+  column_id uuid not null references columns(id) on delete cascade,
+  provider text not null,
+  model text not null,
+  task text not null,
+  total integer not null default 0,
+  completed integer not null default 0,
+  failed integer not null default 0,
+  error text,
+  started_at timestamptz not null,
+  finished_at timestamptz not null default now()
+);
+
+create index idx_generation_runs_dataset on generation_runs(dataset_id, finished_at desc);
+
+alter table generation_runs enable row level security;
+
+create policy "own_generation_runs" on generation_runs for all using (
+  dataset_id in (select id from datasets where user_id = auth.uid())
+);
@@ -0,0 +1,81 @@
+-- Provider pinned per column process
+alter table processes add column provider text check (provider in ('openai', 'mercury'));
+
+-- Per-user settings
+create table user_settings (
+  user_id uuid primary key references auth.users(id) on delete cascade,
+  default_provider text check (default_provider in ('openai', 'mercury')),
+  updated_at timestamptz not null default now()
+);
+
+alter table user_settings enable row level security;
+
+create policy "own_user_settings" on user_settings for all using (user_id = auth.uid());
+
+-- Carry the process provider over when duplicating a column
+create or replace function duplicate_column(
+  source_column_id uuid,
+  copy_cells boolean default false,
+  new_name text default null
+)
+returns uuid
+language plpgsql
+as $$
+declare
+  src columns%rowtype;
+  src_process processes%rowtype;
+  new_column_id uuid;
+  new_process_id uuid;
+begin
+  select * into src from columns where id = source_column_id;
+  if not found then
+    raise exception 'Column % not found', source_column_id;
+  end if;
+
+  -- Shift later columns to make room after the source
+  update columns
+  set position = position + 1
+  where dataset_id = src.dataset_id and position > src.position;
+
+  insert into columns (dataset_id, name, type, kind, visible, position, validation)
+  values (
+    src.dataset_id,
+    coalesce(new_name, src.name || ' (copy)'),
+    src.type,
+    src.kind,
+    src.visible,
+    src.position + 1,
+    src.validation
+  )
+  returning id into new_column_id;
+
+  select * into src_process from processes where column_id = source_column_id;
+  if found then
+    insert into processes (column_id, prompt, model, task, search_enabled, image_column_id, provider)
+    values (
+      new_column_id,
+      src_process.prompt,
+      src_process.model,
+      src_process.task,
+      src_process.search_enabled,
+      src_process.image_column_id,
+      src_process.provider
+    )
+    returning id into new_process_id;
+
+    insert into process_columns (process_id, column_id)
+    select new_process_id, pc.column_id
+    from process_columns pc
+    where pc.process_id = src_process.id;
+  end if;
+
+  if copy_cells then
+    insert into cell_values (dataset_id, column_id, row_idx, value)
+    select cv.dataset_id, new_column_id, cv.row_idx, cv.value
+    from cell_values cv
+    where cv.column_id = source_column_id;
+  end if;
+
+  return new_column_id;
+end;
+$$;
@@ -0,0 +1,47 @@
+-- Point-in-time copies of a column's values, for comparing generation runs
+create table column_snapshots (
+  id uuid primary key default gen_random_uuid(),
+  column_id uuid not null references columns(id) on delete cascade,
+  label text,
+  created_at timestamptz not null default now()
+);
+
+create table column_snapshot_values (
+  snapshot_id uuid not null references column_snapshots(id) on delete cascade,
+  row_idx integer not null,
+  value jsonb,
+  primary key (snapshot_id, row_idx)
+);
+
+create index idx_column_snapshots_column on column_snapshots(column_id, created_at desc);
+
+alter table column_snapshots enable row level security;
+alter table column_snapshot_values enable row level security;
+
+create policy "own_column_snapshots" on column_snapshots for all using (
+  column_id in (select c.id from columns c join datasets d on c.dataset_id = d.id where d.user_id = auth.uid())
+);
+create policy "own_column_snapshot_values" on column_snapshot_values for all using (
+  snapshot_id in (select s.id from column_snapshots s join columns c on s.column_id = c.id join datasets d on c.dataset_id = d.id where d.user_id = auth.uid())
+);
+
+-- RPC function to copy a column's current values into a new snapshot
+create or replace function snapshot_column(target_column_id uuid, snapshot_label text default null)
+returns uuid
+language plpgsql
+as $$
+declare
+  new_snapshot_id uuid;
+begin
+  insert into column_snapshots (column_id, label)
+  values (target_column_id, snapshot_label)
+  returning id into new_snapshot_id;
+
+  insert into column_snapshot_values (snapshot_id, row_idx, value)
+  select new_snapshot_id, cv.row_idx, cv.value
+  from cell_values cv
+  where cv.column_id = target_column_id;
+
+  return new_snapshot_id;
+end;
+$$;
@@ -0,0 +1,64 @@
+-- RPC function to split a column's values by a delimiter into new columns placed after it
+create or replace function split_column(
+  source_column_id uuid,
+  delimiter text,
+  new_column_names text[],
+  overflow text default 'drop'
+)
+returns uuid[]
+language plpgsql
+as $$
+declare
+  src columns%rowtype;
+  part_count integer := coalesce(array_length(new_column_names, 1), 0);
+  new_ids uuid[] := '{}';
+  new_id uuid;
+  i integer;
+begin
+  if part_count = 0 then
+    raise exception 'At least one new column name is required';
+  end if;
+  if overflow not in ('drop', 'merge') then
+    raise exception 'overflow must be ''drop'' or ''merge''';
+  end if;
+
+  select * into src from columns where id = source_column_id;
+  if not found then
+    raise exception 'Column % not found', source_column_id;
+  end if;
+
+  -- Shift later columns to make room after the source
+  update columns
+  set position = position + part_count
+  where dataset_id = src.dataset_id and position > src.position;
+
+  for i in 1..part_count loop
+    insert into columns (dataset_id, name, type, kind, position)
+    values (src.dataset_id, new_column_names[i], 'text', 'static', src.position + i)
+    returning id into new_id;
+
+    new_ids := new_ids || new_id;
+
+    -- Rows with fewer parts than columns leave the extras empty;
+    -- with 'merge', any surplus parts are joined into the last column
+    insert into cell_values (dataset_id, column_id, row_idx, value)
+    select
+      cv.dataset_id,
+      new_id,
+      cv.row_idx,
+      to_jsonb(
+        case
+          when i = part_count and overflow = 'merge'
+            then array_to_string(p.parts[i:], delimiter)
+          else p.parts[i]
+        end
+      )
+    from cell_values cv,
+      lateral (select string_to_array(cv.value #>> '{}', delimiter) as parts) p
+    where cv.column_id = source_column_id
+      and cardinality(p.parts) >= i;
+  end loop;
+
+  return new_ids;
+end;
+$$;
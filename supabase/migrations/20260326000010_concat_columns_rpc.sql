@@ -0,0 +1,54 @@
+-- RPC function to build a new input column by joining several columns' values
+create or replace function concat_columns(
+  target_dataset_id uuid,
+  source_column_ids uuid[],
+  separator text,
+  new_column_name text,
+  skip_empty boolean default true
+)
+returns uuid
+language plpgsql
+as $$
+declare
+  new_column_id uuid;
+  next_position integer;
+begin
+  if coalesce(array_length(source_column_ids, 1), 0) = 0 then
+    raise exception 'At least one source column is required';
+  end if;
+
+  if (
+    select count(*) from columns
+    where id = any(source_column_ids) and dataset_id = target_dataset_id
+  ) <> (select count(distinct u) from unnest(source_column_ids) u) then
+    raise exception 'All source columns must belong to dataset %', target_dataset_id;
+  end if;
+
+  select coalesce(max(position), -1) + 1 into next_position
+  from columns where dataset_id = target_dataset_id;
+
+  insert into columns (dataset_id, name, type, kind, position)
+  values (target_dataset_id, new_column_name, 'text', 'static', next_position)
+  returning id into new_column_id;
+
+  insert into cell_values (dataset_id, column_id, row_idx, value)
+  select
+    target_dataset_id,
+    new_column_id,
+    r.row_idx,
+    to_jsonb(string_agg(coalesce(cv.value #>> '{}', ''), separator order by c.ord))
+  from (
+    select distinct row_idx from cell_values
+    where dataset_id = target_dataset_id and column_id = any(source_column_ids)
+  ) r
+  cross join unnest(source_column_ids) with ordinality as c(column_id, ord)
+  left join cell_values cv
+    on cv.dataset_id = target_dataset_id
+    and cv.column_id = c.column_id
+    and cv.row_idx = r.row_idx
+  where not skip_empty or coalesce(cv.value #>> '{}', '') <> ''
+  group by r.row_idx;
+
+  return new_column_id;
+end;
+$$;
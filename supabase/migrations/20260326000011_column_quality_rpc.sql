@@ -0,0 +1,32 @@
+-- RPC function computing completeness and error metrics for a column
+create or replace function column_quality(target_column_id uuid)
+returns table (
+  row_count bigint,
+  non_empty_pct numeric,
+  error_pct numeric,
+  avg_length numeric,
+  distinct_count bigint
+)
+language plpgsql
+as $$
+begin
+  return query
+  with cells as (
+    select v.row_idx, nullif(v.value #>> '{}', '') as text_value
+    from cell_values v
+    where v.column_id = target_column_id
+  ),
+  errors as (
+    select count(*) as errored
+    from column_cells c
+    where c.column_id = target_column_id and c.error is not null
+  )
+  select
+    count(cells.row_idx),
+    coalesce(round(100.0 * count(cells.text_value) / nullif(count(cells.row_idx), 0), 2), 0),
+    coalesce(round(100.0 * max(errors.errored) / nullif(count(cells.row_idx), 0), 2), 0),
+    coalesce(round(avg(length(cells.text_value)), 2), 0),
+    count(distinct cells.text_value)
+  from cells cross join errors;
+end;
+$$;
@@ -0,0 +1,5 @@
+-- Materialized prompt that produced each generated cell, kept only when the
+-- user opts in
+alter table column_cells add column prompt_used text;
+
+alter table user_settings add column store_prompts boolean not null default false;
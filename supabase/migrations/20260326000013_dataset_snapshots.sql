@@ -0,0 +1,192 @@
+-- Named point-in-time copies of a whole dataset: columns, processes and cells
+create table dataset_snapshots (
+  id uuid primary key default gen_random_uuid(),
+  dataset_id uuid not null references datasets(id) on delete cascade,
+  label text,
+  dataset_name text not null,
+  created_at timestamptz not null default now()
+);
+
+-- Rows are kept as jsonb so later schema additions are captured too
+create table dataset_snapshot_columns (
+  snapshot_id uuid not null references dataset_snapshots(id) on delete cascade,
+  data jsonb not null
+);
+
+create table dataset_snapshot_processes (
+  snapshot_id uuid not null references dataset_snapshots(id) on delete cascade,
+  data jsonb not null
+);
+
+create table dataset_snapshot_process_columns (
+  snapshot_id uuid not null references dataset_snapshots(id) on delete cascade,
+  process_id uuid not null,
+  column_id uuid not null
+);
+
+create table dataset_snapshot_cell_meta (
+  snapshot_id uuid not null references dataset_snapshots(id) on delete cascade,
+  data jsonb not null
+);
+
+create table dataset_snapshot_cells (
+  snapshot_id uuid not null references dataset_snapshots(id) on delete cascade,
+  column_id uuid not null,
+  row_idx integer not null,
+  value jsonb,
+  primary key (snapshot_id, column_id, row_idx)
+);
+
+create index idx_dataset_snapshots_dataset on dataset_snapshots(dataset_id, created_at desc);
+create index idx_dataset_snapshot_columns on dataset_snapshot_columns(snapshot_id);
+create index idx_dataset_snapshot_processes on dataset_snapshot_processes(snapshot_id);
+create index idx_dataset_snapshot_process_columns on dataset_snapshot_process_columns(snapshot_id);
+create index idx_dataset_snapshot_cell_meta on dataset_snapshot_cell_meta(snapshot_id);
+
+alter table dataset_snapshots enable row level security;
+alter table dataset_snapshot_columns enable row level security;
+alter table dataset_snapshot_processes enable row level security;
+alter table dataset_snapshot_process_columns enable row level security;
+alter table dataset_snapshot_cell_meta enable row level security;
+alter table dataset_snapshot_cells enable row level security;
+
+create policy "own_dataset_snapshots" on dataset_snapshots for all using (
+  dataset_id in (select id from datasets where user_id = auth.uid())
+);
+create policy "own_dataset_snapshot_columns" on dataset_snapshot_columns for all using (
+  snapshot_id in (select s.id from dataset_snapshots s join datasets d on s.dataset_id = d.id where d.user_id = auth.uid())
+);
+create policy "own_dataset_snapshot_processes" on dataset_snapshot_processes for all using (
+  snapshot_id in (select s.id from dataset_snapshots s join datasets d on s.dataset_id = d.id where d.user_id = auth.uid())
+);
+create policy "own_dataset_snapshot_process_columns" on dataset_snapshot_process_columns for all using (
+  snapshot_id in (select s.id from dataset_snapshots s join datasets d on s.dataset_id = d.id where d.user_id = auth.uid())
+);
+create policy "own_dataset_snapshot_cell_meta" on dataset_snapshot_cell_meta for all using (
+  snapshot_id in (select s.id from dataset_snapshots s join datasets d on s.dataset_id = d.id where d.user_id = auth.uid())
+);
+create policy "own_dataset_snapshot_cells" on dataset_snapshot_cells for all using (
+  snapshot_id in (select s.id from dataset_snapshots s join datasets d on s.dataset_id = d.id where d.user_id = auth.uid())
+);
+
+-- RPC function to copy a dataset's full current state into a new snapshot
+create or replace function snapshot_dataset(target_dataset_id uuid, snapshot_label text default null)
+returns uuid
+language plpgsql
+as $$
+declare
+  new_snapshot_id uuid;
+begin
+  insert into dataset_snapshots (dataset_id, label, dataset_name)
+  select d.id, snapshot_label, d.name
+  from datasets d
+  where d.id = target_dataset_id
+  returning id into new_snapshot_id;
+
+  if new_snapshot_id is null then
+    raise exception 'Dataset % not found', target_dataset_id;
+  end if;
+
+  insert into dataset_snapshot_columns (snapshot_id, data)
+  select new_snapshot_id, to_jsonb(c)
+  from columns c
+  where c.dataset_id = target_dataset_id;
+
+  insert into dataset_snapshot_processes (snapshot_id, data)
+  select new_snapshot_id, to_jsonb(p)
+  from processes p
+  join columns c on c.id = p.column_id
+  where c.dataset_id = target_dataset_id;
+
+  insert into dataset_snapshot_process_columns (snapshot_id, process_id, column_id)
+  select new_snapshot_id, pc.process_id, pc.column_id
+  from process_columns pc
+  join processes p on p.id = pc.process_id
+  join columns c on c.id = p.column_id
+  where c.dataset_id = target_dataset_id;
+
+  insert into dataset_snapshot_cell_meta (snapshot_id, data)
+  select new_snapshot_id, to_jsonb(cc)
+  from column_cells cc
+  join columns c on c.id = cc.column_id
+  where c.dataset_id = target_dataset_id;
+
+  insert into dataset_snapshot_cells (snapshot_id, column_id, row_idx, value)
+  select new_snapshot_id, cv.column_id, cv.row_idx, cv.value
+  from cell_values cv
+  where cv.dataset_id = target_dataset_id;
+
+  return new_snapshot_id;
+end;
+$$;
+
+-- RPC function to replace a dataset's live state with a snapshot. Columns
+-- keep their ids, so column history outside the snapshot stays attached.
+create or replace function restore_dataset_snapshot(target_snapshot_id uuid)
+returns uuid
+language plpgsql
+as $$
+declare
+  snap dataset_snapshots%rowtype;
+begin
+  select * into snap from dataset_snapshots where id = target_snapshot_id;
+  if not found then
+    raise exception 'Snapshot % not found', target_snapshot_id;
+  end if;
+
+  update datasets set name = snap.dataset_name where id = snap.dataset_id;
+
+  -- Processes first: they may point at columns that are about to go
+  delete from processes p
+  using columns c
+  where c.id = p.column_id and c.dataset_id = snap.dataset_id;
+
+  delete from columns c
+  where c.dataset_id = snap.dataset_id
+    and c.id not in (
+      select (sc.data->>'id')::uuid
+      from dataset_snapshot_columns sc
+      where sc.snapshot_id = target_snapshot_id
+    );
+
+  insert into columns
+  select (jsonb_populate_record(null::columns, sc.data)).*
+  from dataset_snapshot_columns sc
+  where sc.snapshot_id = target_snapshot_id
+  on conflict (id) do update set
+    name = excluded.name,
+    type = excluded.type,
+    kind = excluded.kind,
+    visible = excluded.visible,
+    position = excluded.position,
+    validation = excluded.validation;
+
+  delete from cell_values where dataset_id = snap.dataset_id;
+
+  insert into cell_values (dataset_id, column_id, row_idx, value)
+  select snap.dataset_id, sc.column_id, sc.row_idx, sc.value
+  from dataset_snapshot_cells sc
+  where sc.snapshot_id = target_snapshot_id;
+
+  delete from column_cells cc
+  using columns c
+  where c.id = cc.column_id and c.dataset_id = snap.dataset_id;
+
+  insert into column_cells
+  select (jsonb_populate_record(null::column_cells, sm.data)).*
+  from dataset_snapshot_cell_meta sm
+  where sm.snapshot_id = target_snapshot_id;
+
+  insert into processes
+  select (jsonb_populate_record(null::processes, sp.data)).*
+  from dataset_snapshot_processes sp
+  where sp.snapshot_id = target_snapshot_id;
+
+  insert into process_columns (process_id, column_id)
+  select spc.process_id, spc.column_id
+  from dataset_snapshot_process_columns spc
+  where spc.snapshot_id = target_snapshot_id;
+
+  return snap.dataset_id;
+end;
+$$;
@@ -0,0 +1,2 @@
+-- Hash of the inputs a cell was generated from, for detecting stale cells
+alter table column_cells add column prompt_hash text;
@@ -0,0 +1,45 @@
+-- RPC function to rename a column and, optionally, rewrite every {{Old Name}}
+-- reference (including {{fn:Old Name}} and {{fn:Old Name:n}}) in the other
+-- prompts of its dataset
+create or replace function rename_column(
+  target_column_id uuid,
+  new_name text,
+  rewrite_refs boolean default true
+)
+returns integer
+language plpgsql
+as $$
+declare
+  src columns%rowtype;
+  name_pattern text;
+  replacement text;
+  rewritten integer := 0;
+begin
+  select * into src from columns where id = target_column_id;
+  if not found then
+    raise exception 'Column % not found', target_column_id;
+  end if;
+
+  update columns set name = new_name where id = target_column_id;
+
+  if rewrite_refs and src.name <> new_name then
+    -- Match the whole tag so names that are substrings of others are untouched
+    name_pattern := '\{\{(\s*(?:(?:upper|lower|truncate):)?)'
+      || regexp_replace(src.name, '([.*+?^${}()|\[\]\\])', '\\\1', 'g')
+      || '((?::\d+)?\s*)\}\}';
+    replacement := '{{\1' || replace(new_name, '\', '\\') || '\2}}';
+
+    update processes p
+    set prompt = regexp_replace(p.prompt, name_pattern, replacement, 'g')
+    from columns c
+    where c.id = p.column_id
+      and c.dataset_id = src.dataset_id
+      and c.id <> target_column_id
+      and p.prompt ~ name_pattern;
+
+    get diagnostics rewritten = row_count;
+  end if;
+
+  return rewritten;
+end;
+$$;
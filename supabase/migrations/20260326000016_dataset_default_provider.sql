@@ -0,0 +1,2 @@
+-- Provider used for a dataset's columns that don't pin one
+alter table datasets add column default_provider text check (default_provider in ('openai', 'mercury'));
@@ -0,0 +1,24 @@
+-- RPC function returning a random sample of a dataset's row indices. With a
+-- seed the same sample comes back every time.
+create or replace function sample_rows(
+  target_dataset_id uuid,
+  sample_size integer,
+  sample_seed text default null
+)
+returns setof integer
+language plpgsql
+as $$
+begin
+  return query
+  select r.row_idx
+  from (
+    select distinct cv.row_idx
+    from cell_values cv
+    where cv.dataset_id = target_dataset_id
+  ) r
+  order by
+    case when sample_seed is null then random()::text
+    else md5(sample_seed || ':' || r.row_idx) end
+  limit sample_size;
+end;
+$$;
@@ -0,0 +1,20 @@
+-- Generation runs in flight, so a run cut short by a crash or restart can be
+-- resumed from its last completed row. Rows are removed when a run finishes.
+create table active_runs (
+  id uuid primary key,
+  dataset_id uuid not null references datasets(id) on delete cascade,
+  column_id uuid not null references columns(id) on delete cascade,
+  request jsonb not null,
+  end_row integer not null,
+  last_completed_row integer,
+  started_at timestamptz not null default now(),
+  updated_at timestamptz not null default now()
+);
+
+create index idx_active_runs_dataset on active_runs(dataset_id);
+
+alter table active_runs enable row level security;
+
+create policy "own_active_runs" on active_runs for all using (
+  dataset_id in (select id from datasets where user_id = auth.uid())
+);
@@ -0,0 +1,29 @@
+-- RPC function computing pairwise Pearson correlation between numeric columns.
+-- Each pair only uses rows where both values parse as numbers.
+create or replace function column_correlation(target_column_ids uuid[])
+returns table (
+  column_a uuid,
+  column_b uuid,
+  correlation double precision,
+  sample_size bigint
+)
+language plpgsql
+as $$
+begin
+  return query
+  with numeric_cells as (
+    select v.column_id, v.row_idx, (v.value #>> '{}')::double precision as num
+    from cell_values v
+    where v.column_id = any(target_column_ids)
+      and (v.value #>> '{}') ~ '^\s*[-+]?(\d+\.?\d*|\.\d+)([eE][-+]?\d+)?\s*$'
+  )
+  select
+    a.column_id,
+    b.column_id,
+    corr(a.num, b.num),
+    count(*)
+  from numeric_cells a
+  join numeric_cells b on b.row_idx = a.row_idx and b.column_id > a.column_id
+  group by a.column_id, b.column_id;
+end;
+$$;
@@ -0,0 +1,59 @@
+-- RPC function to switch a column between static and dynamic in one
+-- transaction. Becoming dynamic requires a prompt and can reset the existing
+-- values to pending; becoming static drops the process and keeps the values.
+create or replace function change_column_kind(
+  target_column_id uuid,
+  new_kind text,
+  new_prompt text default null,
+  new_model text default null,
+  new_provider text default null,
+  reference_ids uuid[] default '{}',
+  clear_cells boolean default false
+)
+returns void
+language plpgsql
+as $$
+declare
+  new_process_id uuid;
+begin
+  if new_kind not in ('static', 'dynamic') then
+    raise exception 'Unknown column kind %', new_kind;
+  end if;
+
+  update columns set kind = new_kind where id = target_column_id;
+  if not found then
+    raise exception 'Column % not found', target_column_id;
+  end if;
+
+  if new_kind = 'static' then
+    delete from processes where column_id = target_column_id;
+    return;
+  end if;
+
+  if coalesce(trim(new_prompt), '') = '' then
+    raise exception 'A prompt is required to make a column dynamic';
+  end if;
+
+  insert into processes (column_id, prompt, model, task, provider)
+  values (
+    target_column_id,
+    new_prompt,
+    coalesce(new_model, 'gpt-4o-mini'),
+    'text-generation',
+    new_provider
+  )
+  on conflict (column_id) do update
+    set prompt = excluded.prompt,
+        model = excluded.model,
+        provider = excluded.provider
+  returning id into new_process_id;
+
+  delete from process_columns where process_id = new_process_id;
+  insert into process_columns (process_id, column_id)
+  select new_process_id, ref from unnest(reference_ids) as ref;
+
+  if clear_cells then
+    perform reset_column_cells(target_column_id);
+  end if;
+end;
+$$;
@@ -0,0 +1,2 @@
+-- Long-form markdown documentation of a dataset's purpose and methodology
+alter table datasets add column notes text;
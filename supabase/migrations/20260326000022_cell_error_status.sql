@@ -0,0 +1,2 @@
+-- HTTP status of the provider response that failed a cell, when there was one
+alter table column_cells add column error_status integer;
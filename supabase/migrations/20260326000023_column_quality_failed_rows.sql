@@ -0,0 +1,39 @@
+-- Count rows that only have cell metadata too, so cells that failed before
+-- ever storing a value show up in row_count and error_pct
+create or replace function column_quality(target_column_id uuid)
+returns table (
+  row_count bigint,
+  non_empty_pct numeric,
+  error_pct numeric,
+  avg_length numeric,
+  distinct_count bigint
+)
+language plpgsql
+as $$
+begin
+  return query
+  with row_indices as (
+    select v.row_idx from cell_values v where v.column_id = target_column_id
+    union
+    select c.row_idx from column_cells c where c.column_id = target_column_id
+  ),
+  cells as (
+    select
+      r.row_idx,
+      nullif(v.value #>> '{}', '') as text_value,
+      c.error is not null as errored
+    from row_indices r
+    left join cell_values v
+      on v.column_id = target_column_id and v.row_idx = r.row_idx
+    left join column_cells c
+      on c.column_id = target_column_id and c.row_idx = r.row_idx
+  )
+  select
+    count(cells.row_idx),
+    coalesce(round(100.0 * count(cells.text_value) / nullif(count(cells.row_idx), 0), 2), 0),
+    coalesce(round(100.0 * count(*) filter (where cells.errored) / nullif(count(cells.row_idx), 0), 2), 0),
+    coalesce(round(avg(length(cells.text_value)), 2), 0),
+    count(distinct cells.text_value)
+  from cells;
+end;
+$$;
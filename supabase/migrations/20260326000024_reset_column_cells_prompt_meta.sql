@@ -0,0 +1,29 @@
+-- Reset also clears the stored prompt, input hash and error status, so a
+-- reset cell is pending again rather than looking fresh to only_stale runs
+create or replace function reset_column_cells(target_column_id uuid)
+returns integer
+language plpgsql
+as $$
+declare
+  reset_count integer;
+begin
+  update cell_values
+  set value = null
+  where column_id = target_column_id;
+
+  get diagnostics reset_count = row_count;
+
+  update column_cells
+  set
+    error = null,
+    error_status = null,
+    generating = false,
+    validated = false,
+    sources = null,
+    prompt_used = null,
+    prompt_hash = null
+  where column_id = target_column_id;
+
+  return reset_count;
+end;
+$$;
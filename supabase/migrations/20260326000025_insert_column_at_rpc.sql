@@ -0,0 +1,25 @@
+-- RPC function to insert a column at a position, shifting the columns at and
+-- after it in the same transaction
+create or replace function insert_column_at(
+  target_dataset_id uuid,
+  column_name text,
+  column_kind text,
+  at_position integer
+)
+returns uuid
+language plpgsql
+as $$
+declare
+  new_column_id uuid;
+begin
+  update columns
+  set position = position + 1
+  where dataset_id = target_dataset_id and position >= at_position;
+
+  insert into columns (dataset_id, name, kind, position)
+  values (target_dataset_id, column_name, column_kind, at_position)
+  returning id into new_column_id;
+
+  return new_column_id;
+end;
+$$;
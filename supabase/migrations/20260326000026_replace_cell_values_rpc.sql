@@ -0,0 +1,21 @@
+-- RPC function to overwrite many of a column's existing values in one
+-- statement, so a bulk edit applies fully or not at all
+create or replace function replace_cell_values(
+  target_column_id uuid,
+  updates jsonb
+)
+returns integer
+language plpgsql
+as $$
+declare
+  changed integer;
+begin
+  update cell_values cv
+  set value = u.value
+  from jsonb_to_recordset(updates) as u(row_idx integer, value jsonb)
+  where cv.column_id = target_column_id and cv.row_idx = u.row_idx;
+
+  get diagnostics changed = row_count;
+  return changed;
+end;
+$$;